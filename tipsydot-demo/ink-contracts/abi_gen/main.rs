@@ -0,0 +1,413 @@
+//! abi-gen: Solidity ABI and TypeScript bindings from ink! metadata
+//!
+//! Reads the `<crate>.json` metadata produced by `cargo contract build` and
+//! writes, for every contract found:
+//! - `<out>/<crate>/<crate>.json`: Hardhat-style artifact with the ABI
+//!   and message selectors
+//! - `<out>/<crate>/<crate>.ts`: typed bindings (arguments, return types,
+//!   events and error enums)
+//!
+//! Runs entirely offline against already built artifacts:
+//!
+//! ```bash
+//! cargo run -p abi_gen -- --target-dir target/ink --out-dir ../artifacts/ink tipping_v6
+//! ```
+
+mod metadata;
+mod solidity;
+mod typescript;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage: abi-gen [--target-dir <dir>] [--out-dir <dir>] [crate ...]
+
+Options:
+  --target-dir <dir>  Directory containing ink! build output (default: target/ink)
+  --out-dir <dir>     Directory to write artifacts into (default: ../artifacts/ink)
+  -h, --help          Print this help
+
+Without crate names, every metadata file found in the target directory is used.";
+
+struct Options {
+    target_dir: PathBuf,
+    out_dir: PathBuf,
+    crates: Vec<String>,
+}
+
+fn parse_args(args: impl Iterator<Item = String>) -> Result<Option<Options>, String> {
+    let mut options = Options {
+        target_dir: PathBuf::from("target/ink"),
+        out_dir: PathBuf::from("../artifacts/ink"),
+        crates: Vec::new(),
+    };
+
+    let mut args = args;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--target-dir" => {
+                options.target_dir = args.next().ok_or("--target-dir requires a value")?.into();
+            }
+            "--out-dir" => {
+                options.out_dir = args.next().ok_or("--out-dir requires a value")?.into();
+            }
+            flag if flag.starts_with('-') => return Err(format!("unknown option `{}`", flag)),
+            name => options.crates.push(name.to_string()),
+        }
+    }
+
+    Ok(Some(options))
+}
+
+/// Locate `<crate>.json`, either at the top of the target directory
+/// (single-crate builds) or in a per-crate sub-directory (workspace builds).
+fn metadata_path(target_dir: &Path, name: &str) -> Option<PathBuf> {
+    [
+        target_dir.join(name).join(format!("{}.json", name)),
+        target_dir.join(format!("{}.json", name)),
+    ]
+    .into_iter()
+    .find(|path| path.is_file())
+}
+
+/// Every crate name with metadata in the target directory
+fn discover(target_dir: &Path) -> Result<Vec<String>, String> {
+    let entries = fs::read_dir(target_dir)
+        .map_err(|e| format!("cannot read {}: {}", target_dir.display(), e))?;
+
+    let mut names = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = if path.is_dir() {
+            path.file_name().and_then(|n| n.to_str()).map(String::from)
+        } else if path.extension().is_some_and(|ext| ext == "json") {
+            path.file_stem().and_then(|n| n.to_str()).map(String::from)
+        } else {
+            None
+        };
+
+        if let Some(name) = name {
+            if metadata_path(target_dir, &name).is_some() && !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+    names.sort();
+    Ok(names)
+}
+
+fn generate(options: &Options, name: &str) -> Result<PathBuf, String> {
+    let path = metadata_path(&options.target_dir, name).ok_or_else(|| {
+        format!(
+            "no metadata for `{}` in {} (run `cargo contract build` first)",
+            name,
+            options.target_dir.display()
+        )
+    })?;
+
+    let raw = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let json: serde_json::Value =
+        serde_json::from_str(&raw).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let contract = metadata::parse(&json).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+    let out_dir = options.out_dir.join(name);
+    fs::create_dir_all(&out_dir).map_err(|e| format!("{}: {}", out_dir.display(), e))?;
+
+    let artifact = serde_json::to_string_pretty(&solidity::artifact(&contract))
+        .map_err(|e| e.to_string())?;
+    let artifact_path = out_dir.join(format!("{}.json", name));
+    fs::write(&artifact_path, artifact + "\n")
+        .map_err(|e| format!("{}: {}", artifact_path.display(), e))?;
+
+    let bindings_path = out_dir.join(format!("{}.ts", name));
+    fs::write(&bindings_path, typescript::bindings(&contract))
+        .map_err(|e| format!("{}: {}", bindings_path.display(), e))?;
+
+    Ok(out_dir)
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let crates = if options.crates.is_empty() {
+        match discover(&options.target_dir) {
+            Ok(crates) if !crates.is_empty() => crates,
+            Ok(_) => {
+                eprintln!("error: no contract metadata found in {}", options.target_dir.display());
+                return ExitCode::FAILURE;
+            }
+            Err(e) => {
+                eprintln!("error: {}", e);
+                return ExitCode::FAILURE;
+            }
+        }
+    } else {
+        options.crates.clone()
+    };
+
+    let mut failed = false;
+    for name in &crates {
+        match generate(&options, name) {
+            Ok(out_dir) => println!("✅ {} -> {}", name, out_dir.display()),
+            Err(e) => {
+                eprintln!("❌ {}", e);
+                failed = true;
+            }
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::metadata::{self, AbiType};
+    use super::{solidity, typescript};
+    use serde_json::{json, Value};
+
+    /// Trimmed-down metadata in the shape `cargo contract` emits
+    fn sample_metadata() -> Value {
+        json!({
+            "contract": { "name": "tipping_v6" },
+            "spec": {
+                "constructors": [{
+                    "label": "new",
+                    "selector": "0x9bae9d5e",
+                    "payable": false,
+                    "args": [
+                        { "label": "treasury", "type": { "type": 0, "displayName": ["H160"] } },
+                        { "label": "protocol_fee_bps", "type": { "type": 2, "displayName": ["U256"] } }
+                    ],
+                    "docs": ["Constructor with Solidity-compatible signature"]
+                }],
+                "messages": [
+                    {
+                        "label": "register_builder",
+                        "selector": "0x12345678",
+                        "mutates": true,
+                        "payable": true,
+                        "args": [
+                            { "label": "name", "type": { "type": 5, "displayName": ["Vec"] } },
+                            { "label": "address", "type": { "type": 0, "displayName": ["H160"] } }
+                        ],
+                        "returnType": { "type": 9, "displayName": ["ink", "MessageResult"] },
+                        "docs": []
+                    },
+                    {
+                        "label": "get_builder",
+                        "selector": "0x11111111",
+                        "mutates": false,
+                        "payable": false,
+                        "args": [
+                            { "label": "builder_id", "type": { "type": 2, "displayName": ["U256"] } }
+                        ],
+                        "returnType": { "type": 12, "displayName": ["ink", "MessageResult"] },
+                        "docs": []
+                    }
+                ],
+                "events": [{
+                    "label": "TipSent",
+                    "args": [
+                        { "label": "from", "indexed": true, "type": { "type": 0, "displayName": ["H160"] } },
+                        { "label": "amount", "indexed": false, "type": { "type": 2, "displayName": ["U256"] } }
+                    ],
+                    "docs": []
+                }]
+            },
+            "types": [
+                { "id": 0, "type": { "path": ["primitive_types", "H160"],
+                    "def": { "composite": { "fields": [{ "type": 1, "typeName": "[u8; 20]" }] } } } },
+                { "id": 1, "type": { "def": { "array": { "len": 20, "type": 3 } } } },
+                { "id": 2, "type": { "path": ["primitive_types", "U256"],
+                    "def": { "composite": { "fields": [{ "type": 4, "typeName": "[u64; 4]" }] } } } },
+                { "id": 3, "type": { "def": { "primitive": "u8" } } },
+                { "id": 4, "type": { "def": { "array": { "len": 4, "type": 13 } } } },
+                { "id": 5, "type": { "def": { "sequence": { "type": 3 } } } },
+                { "id": 6, "type": { "path": ["tipping_v6", "tipping_v6", "Error"],
+                    "def": { "variant": { "variants": [
+                        { "name": "Unauthorized", "index": 0 },
+                        { "name": "ZeroAddress", "index": 1 }
+                    ] } } } },
+                { "id": 7, "type": { "path": ["Result"], "params": [],
+                    "def": { "variant": { "variants": [
+                        { "name": "Ok", "index": 0, "fields": [{ "type": 2 }] },
+                        { "name": "Err", "index": 1, "fields": [{ "type": 6 }] }
+                    ] } } } },
+                { "id": 8, "type": { "path": ["ink_primitives", "LangError"],
+                    "def": { "variant": { "variants": [{ "name": "CouldNotReadInput", "index": 1 }] } } } },
+                { "id": 9, "type": { "path": ["Result"],
+                    "def": { "variant": { "variants": [
+                        { "name": "Ok", "index": 0, "fields": [{ "type": 7 }] },
+                        { "name": "Err", "index": 1, "fields": [{ "type": 8 }] }
+                    ] } } } },
+                { "id": 10, "type": { "path": ["tipping_v6", "tipping_v6", "Builder"],
+                    "def": { "composite": { "fields": [
+                        { "name": "id", "type": 2 },
+                        { "name": "address", "type": 0 },
+                        { "name": "is_active", "type": 14 }
+                    ] } } } },
+                { "id": 11, "type": { "path": ["Option"],
+                    "def": { "variant": { "variants": [
+                        { "name": "None", "index": 0 },
+                        { "name": "Some", "index": 1, "fields": [{ "type": 10 }] }
+                    ] } } } },
+                { "id": 12, "type": { "path": ["Result"],
+                    "def": { "variant": { "variants": [
+                        { "name": "Ok", "index": 0, "fields": [{ "type": 11 }] },
+                        { "name": "Err", "index": 1, "fields": [{ "type": 8 }] }
+                    ] } } } },
+                { "id": 13, "type": { "def": { "primitive": "u64" } } },
+                { "id": 14, "type": { "def": { "primitive": "bool" } } }
+            ]
+        })
+    }
+
+    #[test]
+    fn resolves_evm_types_and_unwraps_results() {
+        let contract = metadata::parse(&sample_metadata()).unwrap();
+
+        let register = &contract.messages[0];
+        assert_eq!(register.args[0].ty, AbiType::Bytes);
+        assert_eq!(register.args[1].ty, AbiType::Address);
+        assert_eq!(register.output, AbiType::Uint(256));
+        assert!(matches!(
+            register.error,
+            Some(AbiType::Enum { ref name, ref variants }) if name == "Error" && variants.len() == 2
+        ));
+
+        let get_builder = &contract.messages[1];
+        assert!(get_builder.error.is_none());
+        assert!(matches!(get_builder.output, AbiType::Option(_)));
+    }
+
+    #[test]
+    fn recursive_types_are_rejected() {
+        // `Builder { .., is_active: Option<Builder> }`
+        let mut metadata = sample_metadata();
+        metadata["types"][10]["type"]["def"]["composite"]["fields"][2]["type"] = json!(11);
+
+        // `get_builder` returns `Option<Builder>`, so the cycle closes there
+        let err = metadata::parse(&metadata).unwrap_err();
+        assert_eq!(err, "recursive type Option (id 11)");
+    }
+
+    #[test]
+    fn solidity_abi_has_functions_events_and_errors() {
+        let contract = metadata::parse(&sample_metadata()).unwrap();
+        let abi = solidity::abi(&contract);
+
+        let register = abi.iter().find(|e| e["name"] == "register_builder").unwrap();
+        assert_eq!(register["stateMutability"], "payable");
+        assert_eq!(register["inputs"][0]["type"], "bytes");
+        assert_eq!(register["inputs"][1]["type"], "address");
+        assert_eq!(register["outputs"][0]["type"], "uint256");
+
+        let get_builder = abi.iter().find(|e| e["name"] == "get_builder").unwrap();
+        assert_eq!(get_builder["stateMutability"], "view");
+        assert_eq!(get_builder["outputs"][0]["type"], "tuple");
+        assert_eq!(get_builder["outputs"][0]["components"][1]["components"][2]["name"], "is_active");
+
+        let event = abi.iter().find(|e| e["type"] == "event").unwrap();
+        assert_eq!(event["inputs"][0]["indexed"], true);
+
+        let errors: Vec<&Value> = abi.iter().filter(|e| e["type"] == "error").collect();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0]["name"], "Unauthorized");
+    }
+
+    #[test]
+    fn typescript_bindings_are_typed() {
+        let contract = metadata::parse(&sample_metadata()).unwrap();
+        let ts = typescript::bindings(&contract);
+
+        assert!(ts.contains("export type TippingV6Error = \"Unauthorized\" | \"ZeroAddress\";"));
+        assert!(ts.contains("export interface Builder {\n  id: bigint;\n  address: Address;\n  is_active: boolean;\n}"));
+        assert!(ts.contains("      name: Hex;\n      address: Address;"));
+        assert!(ts.contains("    returns: Builder | null;"));
+        assert!(ts.contains("    error: TippingV6Error;"));
+        assert!(ts.contains("  register_builder: \"0x12345678\","));
+        assert!(ts.contains("export const TippingV6Abi = ["));
+    }
+
+    /// Metadata with ink! trait messages, as `psp22_usdc` emits them
+    fn trait_metadata() -> Value {
+        let message = |label: &str, selector: &str| {
+            json!({
+                "label": label,
+                "selector": selector,
+                "mutates": false,
+                "payable": false,
+                "args": [],
+                "returnType": { "type": 1, "displayName": ["ink", "MessageResult"] },
+                "docs": []
+            })
+        };
+
+        json!({
+            "contract": { "name": "psp22_usdc" },
+            "spec": {
+                "constructors": [],
+                "messages": [
+                    message("PSP22::total_supply", "0x162df8c2"),
+                    message("PSP22::transfer", "0xdb20f9f5"),
+                    message("transfer", "0x84a15da1"),
+                    message("PSP34::transfer", "0x3128d61b")
+                ],
+                "events": []
+            },
+            "types": [
+                { "id": 0, "type": { "def": { "primitive": "u128" } } },
+                { "id": 1, "type": { "path": ["Result"],
+                    "def": { "variant": { "variants": [
+                        { "name": "Ok", "index": 0, "fields": [{ "type": 0 }] },
+                        { "name": "Err", "index": 1, "fields": [{ "type": 2 }] }
+                    ] } } } },
+                { "id": 2, "type": { "path": ["ink_primitives", "LangError"],
+                    "def": { "variant": { "variants": [{ "name": "CouldNotReadInput", "index": 1 }] } } } }
+            ]
+        })
+    }
+
+    #[test]
+    fn trait_messages_get_identifier_names() {
+        let contract = metadata::parse(&trait_metadata()).unwrap();
+        let names: Vec<&str> = contract.messages.iter().map(|m| m.name.as_str()).collect();
+
+        // Unique names drop the trait; clashing ones keep it
+        assert_eq!(names, ["total_supply", "PSP22_transfer", "transfer", "PSP34_transfer"]);
+        assert_eq!(contract.messages[1].label, "PSP22::transfer");
+
+        let abi = solidity::abi(&contract);
+        let functions: Vec<&Value> = abi.iter().filter(|e| e["type"] == "function").collect();
+        assert_eq!(functions[0]["name"], "total_supply");
+        assert_eq!(functions[1]["name"], "PSP22_transfer");
+
+        let ts = typescript::bindings(&contract);
+        assert!(!ts.contains("::"));
+        assert!(ts.contains("  PSP22_transfer: {"));
+        assert!(ts.contains("  total_supply: \"0x162df8c2\","));
+    }
+
+    #[test]
+    fn pascal_case_works() {
+        assert_eq!(metadata::pascal_case("tipping_v6"), "TippingV6");
+        assert_eq!(metadata::pascal_case("psp22_usdc"), "Psp22Usdc");
+    }
+}
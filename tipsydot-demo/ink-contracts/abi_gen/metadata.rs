@@ -0,0 +1,418 @@
+//! Reading ink! contract metadata and resolving its SCALE type registry
+//! into ABI-level types.
+
+use serde_json::Value;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// An ABI-level type resolved from the ink! type registry
+#[derive(Debug, Clone, PartialEq)]
+pub enum AbiType {
+    Bool,
+    Uint(u16),
+    Int(u16),
+    String,
+    Address,
+    Bytes,
+    FixedBytes(usize),
+    Array(Box<AbiType>),
+    FixedArray(Box<AbiType>, usize),
+    /// Struct (named) or tuple (unnamed)
+    Tuple {
+        name: Option<String>,
+        fields: Vec<(String, AbiType)>,
+    },
+    Option(Box<AbiType>),
+    /// Fieldless enum, encoded as its variant index
+    Enum {
+        name: String,
+        variants: Vec<String>,
+    },
+    /// Enum with payload-carrying variants (e.g. a nested `Result`)
+    Variant {
+        name: String,
+        variants: Vec<(String, Vec<(String, AbiType)>)>,
+    },
+    Unit,
+}
+
+/// A message or constructor argument / event field
+#[derive(Debug, Clone)]
+pub struct Param {
+    pub name: String,
+    pub ty: AbiType,
+    pub indexed: bool,
+}
+
+/// A callable entry point (message or constructor)
+#[derive(Debug, Clone)]
+pub struct Callable {
+    /// Label from the metadata; trait messages carry their trait, e.g. `PSP22::transfer`
+    pub label: String,
+    /// Identifier the bindings use, see `assign_names`
+    pub name: String,
+    pub selector: String,
+    pub args: Vec<Param>,
+    /// Success type after unwrapping `MessageResult` and `Result`
+    pub output: AbiType,
+    /// Error type of a `Result` return, if any
+    pub error: Option<AbiType>,
+    pub mutates: bool,
+    pub payable: bool,
+    pub docs: Vec<String>,
+}
+
+/// An event declared by the contract
+#[derive(Debug, Clone)]
+pub struct Event {
+    pub label: String,
+    pub fields: Vec<Param>,
+    pub docs: Vec<String>,
+}
+
+/// Everything the generators need from one contract's metadata
+#[derive(Debug, Clone)]
+pub struct Contract {
+    /// Crate name, e.g. `tipping_v6`
+    pub name: String,
+    pub constructors: Vec<Callable>,
+    pub messages: Vec<Callable>,
+    pub events: Vec<Event>,
+}
+
+/// Type registry lookup over the `types` section of the metadata
+struct Registry<'a> {
+    types: BTreeMap<u64, &'a Value>,
+    /// Ids currently being resolved, to catch types that contain themselves
+    resolving: RefCell<BTreeSet<u64>>,
+}
+
+impl<'a> Registry<'a> {
+    fn new(metadata: &'a Value) -> Result<Self, String> {
+        let entries = metadata["types"]
+            .as_array()
+            .ok_or("metadata has no `types` section")?;
+
+        let mut types = BTreeMap::new();
+        for entry in entries {
+            let id = entry["id"].as_u64().ok_or("type entry without `id`")?;
+            types.insert(id, &entry["type"]);
+        }
+        Ok(Self { types, resolving: RefCell::default() })
+    }
+
+    fn get(&self, id: u64) -> Result<&'a Value, String> {
+        self.types
+            .get(&id)
+            .copied()
+            .ok_or_else(|| format!("unknown type id {}", id))
+    }
+
+    fn path_last(ty: &Value) -> Option<&str> {
+        ty["path"].as_array()?.last()?.as_str()
+    }
+
+    /// Resolve a type id, rejecting recursive types, which have no ABI form
+    fn resolve(&self, id: u64) -> Result<AbiType, String> {
+        if !self.resolving.borrow_mut().insert(id) {
+            let name = self.get(id).ok().and_then(Self::path_last).unwrap_or("<anonymous>");
+            return Err(format!("recursive type {} (id {})", name, id));
+        }
+        let resolved = self.resolve_def(id);
+        self.resolving.borrow_mut().remove(&id);
+        resolved
+    }
+
+    fn resolve_def(&self, id: u64) -> Result<AbiType, String> {
+        let ty = self.get(id)?;
+        let def = &ty["def"];
+
+        match Self::path_last(ty) {
+            Some("H160") => return Ok(AbiType::Address),
+            Some("U256") => return Ok(AbiType::Uint(256)),
+            Some("AccountId") | Some("Hash") | Some("H256") => return Ok(AbiType::FixedBytes(32)),
+            _ => {}
+        }
+
+        if let Some(primitive) = def["primitive"].as_str() {
+            return Self::primitive(primitive);
+        }
+
+        if let Some(inner) = def["compact"]["type"].as_u64() {
+            return self.resolve(inner);
+        }
+
+        if let Some(inner) = def["sequence"]["type"].as_u64() {
+            return Ok(match self.resolve(inner)? {
+                AbiType::Uint(8) => AbiType::Bytes,
+                other => AbiType::Array(Box::new(other)),
+            });
+        }
+
+        if def["array"].is_object() {
+            let len = def["array"]["len"].as_u64().ok_or("array without `len`")? as usize;
+            let inner = def["array"]["type"].as_u64().ok_or("array without `type`")?;
+            return Ok(match self.resolve(inner)? {
+                AbiType::Uint(8) if len <= 32 => AbiType::FixedBytes(len),
+                other => AbiType::FixedArray(Box::new(other), len),
+            });
+        }
+
+        if let Some(items) = def["tuple"].as_array() {
+            if items.is_empty() {
+                return Ok(AbiType::Unit);
+            }
+            let mut fields = Vec::new();
+            for (index, item) in items.iter().enumerate() {
+                let id = item.as_u64().ok_or("tuple item is not a type id")?;
+                fields.push((format!("_{}", index), self.resolve(id)?));
+            }
+            return Ok(AbiType::Tuple { name: None, fields });
+        }
+
+        if def["composite"].is_object() {
+            let fields = self.fields(&def["composite"]["fields"])?;
+            // Newtype wrappers are transparent
+            if fields.len() == 1 && def["composite"]["fields"][0]["name"].is_null() {
+                return Ok(fields.into_iter().next().map(|(_, ty)| ty).unwrap_or(AbiType::Unit));
+            }
+            return Ok(AbiType::Tuple {
+                name: Self::path_last(ty).map(String::from),
+                fields,
+            });
+        }
+
+        if let Some(variants) = def["variant"]["variants"].as_array() {
+            let name = Self::path_last(ty).unwrap_or("Enum").to_string();
+
+            if name == "Option" {
+                let some = variants
+                    .iter()
+                    .find(|v| v["name"] == "Some")
+                    .ok_or("Option without `Some` variant")?;
+                let inner = some["fields"][0]["type"].as_u64().ok_or("Option without payload")?;
+                return Ok(AbiType::Option(Box::new(self.resolve(inner)?)));
+            }
+
+            let mut ordered: Vec<&Value> = variants.iter().collect();
+            ordered.sort_by_key(|v| v["index"].as_u64().unwrap_or(0));
+
+            let fieldless = ordered
+                .iter()
+                .all(|v| v["fields"].as_array().is_none_or(|f| f.is_empty()));
+
+            if fieldless {
+                return Ok(AbiType::Enum {
+                    name,
+                    variants: ordered
+                        .iter()
+                        .filter_map(|v| v["name"].as_str().map(String::from))
+                        .collect(),
+                });
+            }
+
+            let mut resolved = Vec::new();
+            for variant in ordered {
+                let variant_name = variant["name"].as_str().unwrap_or_default().to_string();
+                resolved.push((variant_name, self.fields(&variant["fields"])?));
+            }
+            return Ok(AbiType::Variant { name, variants: resolved });
+        }
+
+        Err(format!("unsupported type definition for id {}", id))
+    }
+
+    fn fields(&self, fields: &Value) -> Result<Vec<(String, AbiType)>, String> {
+        let mut resolved = Vec::new();
+        if let Some(fields) = fields.as_array() {
+            for (index, field) in fields.iter().enumerate() {
+                let id = field["type"].as_u64().ok_or("field without `type`")?;
+                let name = field["name"]
+                    .as_str()
+                    .map(String::from)
+                    .unwrap_or_else(|| format!("_{}", index));
+                resolved.push((name, self.resolve(id)?));
+            }
+        }
+        Ok(resolved)
+    }
+
+    fn primitive(name: &str) -> Result<AbiType, String> {
+        Ok(match name {
+            "bool" => AbiType::Bool,
+            "str" => AbiType::String,
+            "char" => AbiType::Uint(32),
+            "u8" => AbiType::Uint(8),
+            "u16" => AbiType::Uint(16),
+            "u32" => AbiType::Uint(32),
+            "u64" => AbiType::Uint(64),
+            "u128" => AbiType::Uint(128),
+            "u256" => AbiType::Uint(256),
+            "i8" => AbiType::Int(8),
+            "i16" => AbiType::Int(16),
+            "i32" => AbiType::Int(32),
+            "i64" => AbiType::Int(64),
+            "i128" => AbiType::Int(128),
+            "i256" => AbiType::Int(256),
+            other => return Err(format!("unsupported primitive `{}`", other)),
+        })
+    }
+
+    /// Split a message return type into its success and error types.
+    ///
+    /// ink! wraps every return in `MessageResult<T> = Result<T, LangError>`;
+    /// that layer is dropped, then a user-level `Result<T, E>` is split.
+    fn split_return(&self, id: Option<u64>) -> Result<(AbiType, Option<AbiType>), String> {
+        let Some(mut id) = id else {
+            return Ok((AbiType::Unit, None));
+        };
+
+        if let Some((ok, err)) = self.result_parts(id)? {
+            if Self::path_last(self.get(err)?) == Some("LangError") {
+                id = ok;
+            }
+        }
+
+        match self.result_parts(id)? {
+            Some((ok, err)) => Ok((self.resolve(ok)?, Some(self.resolve(err)?))),
+            None => Ok((self.resolve(id)?, None)),
+        }
+    }
+
+    /// Type ids of the `Ok` and `Err` payloads if `id` is a `Result`
+    fn result_parts(&self, id: u64) -> Result<Option<(u64, u64)>, String> {
+        let ty = self.get(id)?;
+        if Self::path_last(ty) != Some("Result") {
+            return Ok(None);
+        }
+
+        let variants = ty["def"]["variant"]["variants"]
+            .as_array()
+            .ok_or("Result without variants")?;
+        let payload = |name: &str| {
+            variants
+                .iter()
+                .find(|v| v["name"] == name)
+                .and_then(|v| v["fields"][0]["type"].as_u64())
+        };
+
+        match (payload("Ok"), payload("Err")) {
+            (Some(ok), Some(err)) => Ok(Some((ok, err))),
+            _ => Err(format!("malformed Result type {}", id)),
+        }
+    }
+}
+
+fn docs(value: &Value) -> Vec<String> {
+    value["docs"]
+        .as_array()
+        .map(|docs| {
+            docs.iter()
+                .filter_map(|line| line.as_str())
+                .map(|line| line.trim().to_string())
+                .filter(|line| !line.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn params(registry: &Registry, args: &Value) -> Result<Vec<Param>, String> {
+    let mut params = Vec::new();
+    if let Some(args) = args.as_array() {
+        for arg in args {
+            let id = arg["type"]["type"].as_u64().ok_or("argument without type id")?;
+            params.push(Param {
+                name: arg["label"].as_str().unwrap_or_default().to_string(),
+                ty: registry.resolve(id)?,
+                indexed: arg["indexed"].as_bool().unwrap_or(false),
+            });
+        }
+    }
+    Ok(params)
+}
+
+fn callable(registry: &Registry, spec: &Value, is_constructor: bool) -> Result<Callable, String> {
+    let label = spec["label"].as_str().ok_or("callable without `label`")?.to_string();
+    let (output, error) = if is_constructor {
+        (AbiType::Unit, None)
+    } else {
+        registry.split_return(spec["returnType"]["type"].as_u64())?
+    };
+
+    Ok(Callable {
+        selector: spec["selector"].as_str().unwrap_or_default().to_string(),
+        args: params(registry, &spec["args"]).map_err(|e| format!("{}: {}", label, e))?,
+        output,
+        error,
+        mutates: is_constructor || spec["mutates"].as_bool().unwrap_or(false),
+        payable: spec["payable"].as_bool().unwrap_or(false),
+        docs: docs(spec),
+        name: label.clone(),
+        label,
+    })
+}
+
+/// Give trait messages identifier-safe names.
+///
+/// `PSP22::transfer` is exposed as `transfer`, unless another message ends
+/// in the same name, in which case it keeps its trait as `PSP22_transfer`.
+fn assign_names(messages: &mut [Callable]) {
+    let short = |label: &str| label.rsplit("::").next().unwrap_or(label).to_string();
+
+    let mut counts = BTreeMap::<String, usize>::new();
+    for message in messages.iter() {
+        *counts.entry(short(&message.label)).or_default() += 1;
+    }
+
+    for message in messages.iter_mut() {
+        let name = short(&message.label);
+        message.name = if counts[&name] > 1 { message.label.replace("::", "_") } else { name };
+    }
+}
+
+/// Parse the JSON metadata emitted by `cargo contract build`
+pub fn parse(metadata: &Value) -> Result<Contract, String> {
+    let registry = Registry::new(metadata)?;
+    let spec = &metadata["spec"];
+
+    let name = metadata["contract"]["name"]
+        .as_str()
+        .ok_or("metadata has no `contract.name`")?
+        .to_string();
+
+    let mut constructors = Vec::new();
+    for constructor in spec["constructors"].as_array().into_iter().flatten() {
+        constructors.push(callable(&registry, constructor, true)?);
+    }
+
+    let mut messages = Vec::new();
+    for message in spec["messages"].as_array().into_iter().flatten() {
+        messages.push(callable(&registry, message, false)?);
+    }
+    assign_names(&mut messages);
+
+    let mut events = Vec::new();
+    for event in spec["events"].as_array().into_iter().flatten() {
+        events.push(Event {
+            label: event["label"].as_str().ok_or("event without `label`")?.to_string(),
+            fields: params(&registry, &event["args"])?,
+            docs: docs(event),
+        });
+    }
+
+    Ok(Contract { name, constructors, messages, events })
+}
+
+/// `tipping_v6` -> `TippingV6`
+pub fn pascal_case(name: &str) -> String {
+    name.split(['_', '-'])
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
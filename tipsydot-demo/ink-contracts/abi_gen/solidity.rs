@@ -0,0 +1,176 @@
+//! Solidity-style ABI JSON generation
+
+use crate::metadata::{AbiType, Callable, Contract, Param};
+use serde_json::{json, Map, Value};
+
+/// Canonical Solidity type string (`uint256`, `tuple[]`, ...)
+pub fn type_name(ty: &AbiType) -> String {
+    match ty {
+        AbiType::Bool => "bool".into(),
+        AbiType::Uint(bits) => format!("uint{}", bits),
+        AbiType::Int(bits) => format!("int{}", bits),
+        AbiType::String => "string".into(),
+        AbiType::Address => "address".into(),
+        AbiType::Bytes => "bytes".into(),
+        AbiType::FixedBytes(len) => format!("bytes{}", len),
+        AbiType::Array(inner) => format!("{}[]", type_name(inner)),
+        AbiType::FixedArray(inner, len) => format!("{}[{}]", type_name(inner), len),
+        AbiType::Tuple { .. } | AbiType::Option(_) => "tuple".into(),
+        AbiType::Enum { .. } => "uint8".into(),
+        // Payload enums are passed through as their SCALE encoding
+        AbiType::Variant { .. } => "bytes".into(),
+        AbiType::Unit => "tuple".into(),
+    }
+}
+
+/// Human-readable `internalType`, mirroring what solc emits
+fn internal_type(ty: &AbiType) -> String {
+    match ty {
+        AbiType::Tuple { name: Some(name), .. } => format!("struct {}", name),
+        AbiType::Option(inner) => format!("struct Option<{}>", internal_type(inner)),
+        AbiType::Enum { name, .. } => format!("enum {}", name),
+        AbiType::Variant { name, .. } => format!("bytes /* SCALE {} */", name),
+        AbiType::Array(inner) => format!("{}[]", internal_type(inner)),
+        AbiType::FixedArray(inner, len) => format!("{}[{}]", internal_type(inner), len),
+        other => type_name(other),
+    }
+}
+
+fn components(ty: &AbiType) -> Option<Vec<Value>> {
+    match ty {
+        AbiType::Tuple { fields, .. } => Some(
+            fields
+                .iter()
+                .map(|(name, ty)| param_json(name, ty, None))
+                .collect(),
+        ),
+        AbiType::Option(inner) => Some(vec![
+            param_json("isSome", &AbiType::Bool, None),
+            param_json("value", inner, None),
+        ]),
+        AbiType::Array(inner) | AbiType::FixedArray(inner, _) => components(inner),
+        _ => None,
+    }
+}
+
+fn param_json(name: &str, ty: &AbiType, indexed: Option<bool>) -> Value {
+    let mut param = Map::new();
+    if let Some(indexed) = indexed {
+        param.insert("indexed".into(), json!(indexed));
+    }
+    param.insert("internalType".into(), json!(internal_type(ty)));
+    param.insert("name".into(), json!(name));
+    param.insert("type".into(), json!(type_name(ty)));
+    if let Some(components) = components(ty) {
+        param.insert("components".into(), json!(components));
+    }
+    Value::Object(param)
+}
+
+fn inputs(params: &[Param]) -> Vec<Value> {
+    params.iter().map(|p| param_json(&p.name, &p.ty, None)).collect()
+}
+
+fn outputs(ty: &AbiType) -> Vec<Value> {
+    match ty {
+        AbiType::Unit => Vec::new(),
+        other => vec![param_json("", other, None)],
+    }
+}
+
+fn state_mutability(callable: &Callable) -> &'static str {
+    match (callable.mutates, callable.payable) {
+        (_, true) => "payable",
+        (true, false) => "nonpayable",
+        (false, false) => "view",
+    }
+}
+
+/// Build the ABI array for a contract
+///
+/// Solidity allows a single constructor, so only `new` (or the first
+/// constructor) is emitted. Every variant of every error enum returned by a
+/// message becomes a custom `error` entry.
+pub fn abi(contract: &Contract) -> Vec<Value> {
+    let mut entries = Vec::new();
+
+    let constructor = contract
+        .constructors
+        .iter()
+        .find(|c| c.label == "new")
+        .or_else(|| contract.constructors.first());
+    if let Some(constructor) = constructor {
+        entries.push(json!({
+            "inputs": inputs(&constructor.args),
+            "stateMutability": state_mutability(constructor),
+            "type": "constructor",
+        }));
+    }
+
+    for message in &contract.messages {
+        entries.push(json!({
+            "inputs": inputs(&message.args),
+            "name": message.name,
+            "outputs": outputs(&message.output),
+            "stateMutability": state_mutability(message),
+            "type": "function",
+        }));
+    }
+
+    for event in &contract.events {
+        let fields: Vec<Value> = event
+            .fields
+            .iter()
+            .map(|f| param_json(&f.name, &f.ty, Some(f.indexed)))
+            .collect();
+        entries.push(json!({
+            "anonymous": false,
+            "inputs": fields,
+            "name": event.label,
+            "type": "event",
+        }));
+    }
+
+    let mut seen = Vec::new();
+    for error in contract.messages.iter().filter_map(|m| m.error.as_ref()) {
+        let variants: Vec<(String, Vec<(String, AbiType)>)> = match error {
+            AbiType::Enum { variants, .. } => {
+                variants.iter().map(|v| (v.clone(), Vec::new())).collect()
+            }
+            AbiType::Variant { variants, .. } => variants.clone(),
+            _ => continue,
+        };
+        for (name, fields) in variants {
+            if seen.contains(&name) {
+                continue;
+            }
+            let inputs: Vec<Value> = fields
+                .iter()
+                .map(|(field, ty)| param_json(field, ty, None))
+                .collect();
+            entries.push(json!({
+                "inputs": inputs,
+                "name": name,
+                "type": "error",
+            }));
+            seen.push(name);
+        }
+    }
+
+    entries
+}
+
+/// Hardhat-style artifact: `{ contractName, abi, selectors }`
+pub fn artifact(contract: &Contract) -> Value {
+    let selectors: Map<String, Value> = contract
+        .messages
+        .iter()
+        .map(|m| (m.name.clone(), json!(m.selector)))
+        .collect();
+
+    json!({
+        "contractName": crate::metadata::pascal_case(&contract.name),
+        "abi": abi(contract),
+        "selectors": selectors,
+    })
+}
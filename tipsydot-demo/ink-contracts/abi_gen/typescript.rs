@@ -0,0 +1,189 @@
+//! Typed TypeScript bindings generation
+
+use crate::metadata::{pascal_case, AbiType, Callable, Contract};
+use crate::solidity;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Collects named struct / enum declarations while mapping types
+struct Declarations {
+    contract: String,
+    items: BTreeMap<String, String>,
+}
+
+impl Declarations {
+    /// `Error` is too generic to export from several modules at once,
+    /// so it is prefixed with the contract name.
+    fn type_name(&self, name: &str) -> String {
+        if name == "Error" {
+            format!("{}Error", self.contract)
+        } else {
+            name.to_string()
+        }
+    }
+
+    fn ts_type(&mut self, ty: &AbiType) -> String {
+        match ty {
+            AbiType::Bool => "boolean".into(),
+            AbiType::Uint(bits) | AbiType::Int(bits) if *bits <= 32 => "number".into(),
+            AbiType::Uint(_) | AbiType::Int(_) => "bigint".into(),
+            AbiType::String => "string".into(),
+            AbiType::Address => "Address".into(),
+            AbiType::Bytes | AbiType::FixedBytes(_) => "Hex".into(),
+            AbiType::Array(inner) | AbiType::FixedArray(inner, _) => {
+                format!("{}[]", self.wrapped(inner))
+            }
+            AbiType::Option(inner) => format!("{} | null", self.ts_type(inner)),
+            AbiType::Unit => "void".into(),
+            AbiType::Tuple { name: None, fields } => {
+                let items: Vec<String> = fields.iter().map(|(_, ty)| self.ts_type(ty)).collect();
+                format!("[{}]", items.join(", "))
+            }
+            AbiType::Tuple { name: Some(name), fields } => {
+                let name = self.type_name(name);
+                if !self.items.contains_key(&name) {
+                    // Reserve the name first so recursive types terminate
+                    self.items.insert(name.clone(), String::new());
+                    let mut body = format!("export interface {} {{\n", name);
+                    for (field, ty) in fields {
+                        let _ = writeln!(body, "  {}: {};", field, self.ts_type(ty));
+                    }
+                    body.push('}');
+                    self.items.insert(name.clone(), body);
+                }
+                name
+            }
+            AbiType::Enum { name, variants } => {
+                let name = self.type_name(name);
+                if !self.items.contains_key(&name) {
+                    let union: Vec<String> = variants.iter().map(|v| format!("\"{}\"", v)).collect();
+                    self.items.insert(
+                        name.clone(),
+                        format!("export type {} = {};", name, union.join(" | ")),
+                    );
+                }
+                name
+            }
+            AbiType::Variant { name, variants } => {
+                let name = self.type_name(name);
+                if !self.items.contains_key(&name) {
+                    self.items.insert(name.clone(), String::new());
+                    let mut arms = Vec::new();
+                    for (variant, fields) in variants {
+                        let payload: Vec<String> = fields
+                            .iter()
+                            .map(|(field, ty)| format!("{}: {}", field, self.ts_type(ty)))
+                            .collect();
+                        if payload.is_empty() {
+                            arms.push(format!("{{ type: \"{}\" }}", variant));
+                        } else {
+                            arms.push(format!("{{ type: \"{}\"; {} }}", variant, payload.join("; ")));
+                        }
+                    }
+                    self.items.insert(
+                        name.clone(),
+                        format!("export type {} =\n  | {};", name, arms.join("\n  | ")),
+                    );
+                }
+                name
+            }
+        }
+    }
+
+    /// Parenthesise unions before appending `[]`
+    fn wrapped(&mut self, ty: &AbiType) -> String {
+        let inner = self.ts_type(ty);
+        if inner.contains(" | ") {
+            format!("({})", inner)
+        } else {
+            inner
+        }
+    }
+}
+
+fn write_docs(out: &mut String, docs: &[String], indent: &str) {
+    if docs.is_empty() {
+        return;
+    }
+    let _ = writeln!(out, "{}/** {} */", indent, docs.join(" "));
+}
+
+fn write_callable(out: &mut String, decls: &mut Declarations, callable: &Callable) {
+    write_docs(out, &callable.docs, "  ");
+    let _ = writeln!(out, "  {}: {{", callable.name);
+    let _ = writeln!(out, "    selector: \"{}\";", callable.selector);
+    if callable.args.is_empty() {
+        let _ = writeln!(out, "    args: {{}};");
+    } else {
+        let _ = writeln!(out, "    args: {{");
+        for arg in &callable.args {
+            let _ = writeln!(out, "      {}: {};", arg.name, decls.ts_type(&arg.ty));
+        }
+        let _ = writeln!(out, "    }};");
+    }
+    let _ = writeln!(out, "    returns: {};", decls.ts_type(&callable.output));
+    let error = match &callable.error {
+        Some(error) => decls.ts_type(error),
+        None => "never".into(),
+    };
+    let _ = writeln!(out, "    error: {};", error);
+    let _ = writeln!(out, "  }};");
+}
+
+/// Render the `.ts` module for a contract
+pub fn bindings(contract: &Contract) -> String {
+    let name = pascal_case(&contract.name);
+    let mut decls = Declarations {
+        contract: name.clone(),
+        items: BTreeMap::new(),
+    };
+
+    let mut messages = String::new();
+    for message in &contract.messages {
+        write_callable(&mut messages, &mut decls, message);
+    }
+
+    let mut constructors = String::new();
+    for constructor in &contract.constructors {
+        write_callable(&mut constructors, &mut decls, constructor);
+    }
+
+    let mut events = String::new();
+    for event in &contract.events {
+        write_docs(&mut events, &event.docs, "  ");
+        let _ = writeln!(events, "  {}: {{", event.label);
+        for field in &event.fields {
+            let _ = writeln!(events, "    {}: {};", field.name, decls.ts_type(&field.ty));
+        }
+        let _ = writeln!(events, "  }};");
+    }
+
+    let abi = serde_json::to_string_pretty(&solidity::abi(contract)).unwrap_or_else(|_| "[]".into());
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "// Generated by abi-gen from the `{}` ink! metadata. Do not edit by hand.\n",
+        contract.name
+    );
+    let _ = writeln!(out, "export type Address = `0x${{string}}`;");
+    let _ = writeln!(out, "export type Hex = `0x${{string}}`;\n");
+
+    for item in decls.items.values() {
+        let _ = writeln!(out, "{}\n", item);
+    }
+
+    let _ = writeln!(out, "export interface {}Constructors {{\n{}}}\n", name, constructors);
+    let _ = writeln!(out, "export interface {}Messages {{\n{}}}\n", name, messages);
+    let _ = writeln!(out, "export interface {}Events {{\n{}}}\n", name, events);
+
+    let _ = writeln!(out, "export const {}Selectors = {{", name);
+    for message in &contract.messages {
+        let _ = writeln!(out, "  {}: \"{}\",", message.name, message.selector);
+    }
+    let _ = writeln!(out, "}} as const satisfies Record<keyof {}Messages, string>;\n", name);
+
+    let _ = writeln!(out, "export const {}Abi = {} as const;", name, abi);
+
+    out
+}
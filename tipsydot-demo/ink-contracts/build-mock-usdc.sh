@@ -0,0 +1,30 @@
+#!/bin/bash
+
+# Compile contracts/MockUSDC.sol for PolkaVM with resolc
+# tipping_compatible's e2e tests deploy the result as the Solidity USDC
+
+set -e
+
+echo "🔨 Building MockUSDC.sol for PolkaVM"
+echo "===================================="
+
+if ! command -v resolc &> /dev/null; then
+    echo "❌ resolc not found. Install it from https://github.com/paritytech/revive/releases"
+    exit 1
+fi
+
+CONTRACTS_DIR="$(cd "$(dirname "$0")/../contracts" && pwd)"
+OUT_DIR="$(mktemp -d)"
+trap 'rm -rf "$OUT_DIR"' EXIT
+
+resolc --bin --overwrite -o "$OUT_DIR" "$CONTRACTS_DIR/MockUSDC.sol"
+
+# resolc names its output `<source>:<contract>.pvm`
+BLOB="$(find "$OUT_DIR" -name '*MockUSDC.pvm' | head -n 1)"
+if [ -z "$BLOB" ]; then
+    echo "❌ resolc produced no MockUSDC blob"
+    exit 1
+fi
+
+cp "$BLOB" "$CONTRACTS_DIR/MockUSDC.polkavm"
+echo "✅ Wrote contracts/MockUSDC.polkavm"
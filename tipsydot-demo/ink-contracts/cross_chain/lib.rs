@@ -1,10 +1,19 @@
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
+pub mod reserve_transfer;
+pub mod ss58;
+
 #[ink::contract]
 pub mod cross_chain {
+    use crate::reserve_transfer::{self, ReserveTransfer, XcmWeight};
+    use crate::ss58::{self, AccountFormat, AddressError, Beneficiary};
+    use evm_interop::assets_precompile;
     use ink::prelude::{string::String, vec::Vec};
+    use ink::primitives::{H160, U256};
+    use ink::env::hash::{Blake2x256, HashOutput};
     use ink::storage::Mapping;
     use scale::{Decode, Encode};
+    use xcm::{IntoVersion, Version, VersionedXcm};
 
     /// Represents a parachain builder with substrate address
     #[derive(Debug, Clone, Encode, Decode)]
@@ -12,37 +21,230 @@ pub mod cross_chain {
     pub struct ParachainBuilder {
         pub name: String,
         pub project: String,
-        pub substrate_address: String, // SS58 encoded address
+        pub substrate_address: String, // SS58 or 0x-prefixed H160 display address
+        pub beneficiary: Beneficiary,  // Decoded account the XCM deposit targets
         pub parachain_id: u32,
         pub total_received: Balance,
         pub active: bool,
+        /// Local account allowed to manage payouts for this builder
+        pub controller: Option<AccountId>,
+    }
+
+    /// Where and how a builder wants to be paid
+    #[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct PayoutPreferences {
+        /// Destination parachain
+        pub parachain_id: u32,
+        /// SS58 or 0x-prefixed H160 display address on the destination
+        pub address: String,
+        /// Decoded account, in the destination's account format
+        pub beneficiary: Beneficiary,
+        /// Asset `donate` sends to this builder
+        pub asset_id: u32,
+        /// Donations in the preferred asset are held until this much is pending (0 = send immediately)
+        pub min_batch: Balance,
+    }
+
+    /// Donations waiting to be paid out together
+    #[derive(Debug, Clone, Default, Encode, Decode, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct PendingPayout {
+        pub amount: Balance,
+        pub transfer_ids: Vec<u64>,
+    }
+
+    /// Default USDC donation limits, matching `SecureXCMDonation.sol`
+    pub const USDC_MIN_DONATION: Balance = 1_000_000; // 1 USDC (6 decimals)
+    pub const USDC_MAX_DONATION: Balance = 100_000_000_000; // 100,000 USDC
+
+    /// Configuration of an asset accepted for cross-chain donations
+    #[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct AssetConfig {
+        /// PSP22 contract for the local representation of the asset, or its
+        /// pallet-assets precompile (see `get_assets_precompile`)
+        pub token: AccountId,
+        pub decimals: u8,
+        pub min_donation: Balance,
+        pub max_donation: Balance,
+        /// Asset ID XCM execution fees are paid in
+        pub fee_asset_id: u32,
+        pub enabled: bool,
+    }
+
+    /// XCM version programs are built in
+    pub const DEFAULT_XCM_VERSION: Version = 4;
+
+    /// Routing and fee parameters of a destination parachain
+    #[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct ParachainConfig {
+        pub name: String,
+        pub account_format: AccountFormat,
+        /// XCM version the transfer program is converted to
+        pub xcm_version: Version,
+        /// Weight limit for execution on the parachain (`None` = unlimited)
+        pub weight_limit: Option<XcmWeight>,
+        /// Most of a donation spent on execution fees (`None` = all of it)
+        pub fee_budget: Option<Balance>,
+        /// Weight charged per XCM instruction (fixed weight bounds)
+        pub unit_weight: XcmWeight,
+        /// Fee-asset units charged per second (10^12 ref time) of execution
+        pub fee_per_second: Balance,
+        pub enabled: bool,
+    }
+
+    /// Default per-instruction weight, as on the system parachains
+    pub const DEFAULT_UNIT_WEIGHT: XcmWeight = XcmWeight { ref_time: 1_000_000_000, proof_size: 65_536 };
+    /// Default execution price: 0.01 USDC per instruction at the default unit weight
+    pub const DEFAULT_FEE_PER_SECOND: Balance = 10_000_000;
+    /// Ref time in one second of execution
+    const REF_TIME_PER_SECOND: u128 = 1_000_000_000_000;
+
+    /// What a donation is expected to cost and deliver
+    #[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct DonationEstimate {
+        /// Execution weight across all remote hops
+        pub weight: XcmWeight,
+        /// Asset the fee is quoted in
+        pub fee_asset_id: u32,
+        /// Execution fees across all remote hops
+        pub fee: Balance,
+        /// Amount the builder receives after fees
+        pub net_amount: Balance,
     }
 
+    /// Blocks a sent transfer may wait for confirmation before it times out
+    pub const DEFAULT_TRANSFER_TIMEOUT: BlockNumber = 600; // ~1 hour at 6s blocks
+
     /// XCM transfer details
     #[derive(Debug, Clone, Encode, Decode)]
-    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
     pub struct XcmTransfer {
         pub from: AccountId,
+        pub builder_id: u32,
         pub to_substrate: String,
+        pub beneficiary: Beneficiary,
         pub parachain_id: u32,
         pub asset_id: u32,
+        /// Token the donation was escrowed in, kept even if the asset's
+        /// configured token changes later
+        pub token: AccountId,
         pub amount: Balance,
         pub timestamp: Timestamp,
+        pub sent_at: BlockNumber,
         pub status: TransferStatus,
+        pub failure_reason: Option<String>,
+        /// Destination block hash the relayers attested to
+        pub dest_block_hash: Option<Hash>,
+        /// Blake2-256 hash of the SCALE-encoded XCM program sent
+        pub message_hash: Option<Hash>,
+        /// Client-supplied key that makes resubmissions idempotent
+        pub idempotency_key: Option<Hash>,
     }
 
-    #[derive(Debug, Clone, Encode, Decode, PartialEq)]
-    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    /// Account on another parachain that sent an inbound donation
+    #[derive(Debug, Clone, Copy, Encode, Decode, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct InboundOrigin {
+        pub para_id: u32,
+        pub account: Beneficiary,
+    }
+
+    /// USDC received at the contract's sovereign location and credited to a builder
+    #[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct InboundDonation {
+        pub origin: InboundOrigin,
+        pub builder_id: u32,
+        pub asset_id: u32,
+        pub amount: Balance,
+        /// Source-chain reference (XCM message topic) used for deduplication
+        pub reference: Hash,
+        /// Relayer or executor that reported the transfer
+        pub credited_by: AccountId,
+        pub timestamp: Timestamp,
+    }
+
+    /// A relayer's report of an inbound donation
+    #[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct InboundAttestation {
+        pub relayer: AccountId,
+        pub origin: InboundOrigin,
+        pub builder_id: u32,
+        pub asset_id: u32,
+        pub amount: Balance,
+    }
+
+    impl InboundAttestation {
+        /// Whether two reports describe the same donation
+        pub fn agrees_with(&self, other: &InboundAttestation) -> bool {
+            self.origin == other.origin
+                && self.builder_id == other.builder_id
+                && self.asset_id == other.asset_id
+                && self.amount == other.amount
+        }
+    }
+
+    /// Outcome of a transfer as observed on the destination chain
+    #[derive(Debug, Clone, Copy, Encode, Decode, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum TransferOutcome {
+        Delivered,
+        Failed,
+    }
+
+    /// A relayer's statement about a transfer
+    #[derive(Debug, Clone, Encode, Decode, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct Attestation {
+        pub relayer: AccountId,
+        pub outcome: TransferOutcome,
+        pub dest_block_hash: Hash,
+    }
+
+    /// Lifecycle of an XCM transfer
+    ///
+    /// `Pending -> Sent -> Confirmed | Failed | TimedOut`, and failed or
+    /// timed-out transfers can then be `Refunded` to the donor. A late
+    /// confirmation still wins over a timeout as long as nothing was refunded.
+    /// A batched donation still `Pending` once its window has passed can be
+    /// refunded straight away, leaving its builder's batch.
+    #[derive(Debug, Clone, Copy, Encode, Decode, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
     pub enum TransferStatus {
         Pending,
         Sent,
         Confirmed,
         Failed,
+        TimedOut,
+        Refunded,
+    }
+
+    impl TransferStatus {
+        /// Whether moving from `self` to `next` is a legal transition
+        pub fn can_transition_to(self, next: TransferStatus) -> bool {
+            use TransferStatus::*;
+            matches!(
+                (self, next),
+                (Pending, Sent)
+                    | (Pending, Refunded)
+                    | (Sent, Confirmed)
+                    | (Sent, Failed)
+                    | (Sent, TimedOut)
+                    | (TimedOut, Confirmed)
+                    | (Failed, Refunded)
+                    | (TimedOut, Refunded)
+            )
+        }
     }
 
     /// Events emitted by the contract
     #[ink(event)]
-    pub struct CrossChainDonation {
+    pub struct DonationMade {
         #[ink(topic)]
         donor: AccountId,
         #[ink(topic)]
@@ -61,6 +263,8 @@ pub mod cross_chain {
         to: String,
         amount: Balance,
         parachain_id: u32,
+        #[ink(topic)]
+        message_hash: Hash,
     }
 
     #[ink(event)]
@@ -70,6 +274,193 @@ pub mod cross_chain {
         success: bool,
     }
 
+    #[ink(event)]
+    pub struct XcmTransferFailed {
+        #[ink(topic)]
+        transfer_id: u64,
+        reason: String,
+    }
+
+    #[ink(event)]
+    pub struct XcmTransferTimedOut {
+        #[ink(topic)]
+        transfer_id: u64,
+        sent_at: BlockNumber,
+    }
+
+    #[ink(event)]
+    pub struct PayoutPreferencesUpdated {
+        #[ink(topic)]
+        builder_id: u32,
+        parachain_id: u32,
+        address: String,
+        asset_id: u32,
+        min_batch: Balance,
+    }
+
+    #[ink(event)]
+    pub struct PayoutQueued {
+        #[ink(topic)]
+        builder_id: u32,
+        #[ink(topic)]
+        transfer_id: u64,
+        pending_amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct PayoutFlushed {
+        #[ink(topic)]
+        builder_id: u32,
+        asset_id: u32,
+        amount: Balance,
+        transfer_count: u32,
+        #[ink(topic)]
+        message_hash: Hash,
+    }
+
+    #[ink(event)]
+    pub struct InboundDonationCredited {
+        #[ink(topic)]
+        inbound_id: u64,
+        #[ink(topic)]
+        reference: Hash,
+        #[ink(topic)]
+        builder_id: u32,
+        origin_para_id: u32,
+        origin_account: Beneficiary,
+        asset_id: u32,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct InboundClaimed {
+        #[ink(topic)]
+        builder_id: u32,
+        asset_id: u32,
+        amount: Balance,
+        parachain_id: u32,
+        #[ink(topic)]
+        message_hash: Hash,
+    }
+
+    #[ink(event)]
+    pub struct InboundExecutorChanged {
+        executor: Option<AccountId>,
+    }
+
+    #[ink(event)]
+    pub struct XcmReserveChanged {
+        #[ink(topic)]
+        reserve: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct TransferAttested {
+        #[ink(topic)]
+        transfer_id: u64,
+        #[ink(topic)]
+        relayer: AccountId,
+        outcome: TransferOutcome,
+        dest_block_hash: Hash,
+    }
+
+    #[ink(event)]
+    pub struct InboundAttested {
+        #[ink(topic)]
+        reference: Hash,
+        #[ink(topic)]
+        relayer: AccountId,
+        builder_id: u32,
+        asset_id: u32,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct AttestationDisputed {
+        #[ink(topic)]
+        transfer_id: u64,
+        #[ink(topic)]
+        relayer: AccountId,
+        outcome: TransferOutcome,
+        dest_block_hash: Hash,
+    }
+
+    #[ink(event)]
+    pub struct RelayerAdded {
+        #[ink(topic)]
+        relayer: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct RelayerRemoved {
+        #[ink(topic)]
+        relayer: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct RelayerThresholdChanged {
+        old_threshold: u32,
+        new_threshold: u32,
+    }
+
+    #[ink(event)]
+    pub struct ParachainRegistered {
+        #[ink(topic)]
+        para_id: u32,
+        name: String,
+        enabled: bool,
+    }
+
+    #[ink(event)]
+    pub struct ParachainStatusChanged {
+        #[ink(topic)]
+        para_id: u32,
+        enabled: bool,
+    }
+
+    #[ink(event)]
+    pub struct AssetConfigured {
+        #[ink(topic)]
+        asset_id: u32,
+        token: AccountId,
+        enabled: bool,
+    }
+
+    #[ink(event)]
+    pub struct DonationLimitsUpdated {
+        #[ink(topic)]
+        asset_id: u32,
+        min_donation: Balance,
+        max_donation: Balance,
+    }
+
+    #[ink(event)]
+    pub struct TransferRefunded {
+        #[ink(topic)]
+        transfer_id: u64,
+        #[ink(topic)]
+        donor: AccountId,
+        amount: Balance,
+    }
+
+    /// Stray tokens recovered by the owner
+    #[ink(event)]
+    pub struct TokensRecovered {
+        #[ink(topic)]
+        token: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+        amount: Balance,
+    }
+
+    /// Stray native balance recovered by the owner
+    #[ink(event)]
+    pub struct NativeRecovered {
+        #[ink(topic)]
+        to: H160,
+        amount: U256,
+    }
+
     /// Errors that can occur
     #[derive(Debug, PartialEq, Eq, Encode, Decode)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
@@ -82,6 +473,38 @@ pub mod cross_chain {
         InvalidParachain,
         XcmExecutionFailed,
         AssetNotSupported,
+        InvalidAddress,
+        AddressPrefixMismatch,
+        InvalidTransition,
+        TransferNotExpired,
+        InvalidTimeout,
+        NotRelayer,
+        RelayerAlreadyExists,
+        AlreadyAttested,
+        InvalidThreshold,
+        TokenTransferFailed,
+        InvalidLimits,
+        ParachainDisabled,
+        UnsupportedXcmVersion,
+        IdempotencyKeyReused,
+        FeeBudgetExceeded,
+        DuplicateReference,
+        PayoutPending,
+        NothingToFlush,
+        BatchBelowMinimum,
+        NotPaused,
+        FundsReserved,
+        EscrowSpent,
+        NothingToClaim,
+    }
+
+    impl From<AddressError> for CrossChainError {
+        fn from(error: AddressError) -> Self {
+            match error {
+                AddressError::Invalid => CrossChainError::InvalidAddress,
+                AddressError::PrefixMismatch => CrossChainError::AddressPrefixMismatch,
+            }
+        }
     }
 
     /// The cross-chain donation contract
@@ -91,8 +514,12 @@ pub mod cross_chain {
         owner: AccountId,
         /// AssetHub parachain ID (usually 1000)
         asset_hub_id: u32,
-        /// USDC asset ID on AssetHub
+        /// USDC asset ID on AssetHub, used by `donate`
         usdc_asset_id: u32,
+        /// Account that keeps the contract's AssetHub balance funded for
+        /// donations escrowed in a PSP22 wrapper, and is paid their escrow
+        /// once they are delivered
+        xcm_reserve: AccountId,
         /// Mapping of builder ID to builder details
         builders: Mapping<u32, ParachainBuilder>,
         /// Number of builders
@@ -101,44 +528,146 @@ pub mod cross_chain {
         transfers: Mapping<u64, XcmTransfer>,
         /// Transfer nonce for unique IDs
         transfer_nonce: u64,
-        /// Supported asset IDs for cross-chain transfers
-        supported_assets: Mapping<u32, bool>,
+        /// Transfer created for each (donor, idempotency key)
+        idempotency_keys: Mapping<(AccountId, Hash), u64>,
+        /// Per-asset configuration for cross-chain transfers
+        assets: Mapping<u32, AssetConfig>,
+        /// Registry of destination parachains
+        parachains: Mapping<u32, ParachainConfig>,
+        /// Registered parachain IDs, in registration order
+        parachain_ids: Vec<u32>,
         /// Contract pause state
         paused: bool,
+        /// Blocks after sending before an unconfirmed transfer times out
+        transfer_timeout: BlockNumber,
+        /// Relayers allowed to attest transfer outcomes
+        relayers: Vec<AccountId>,
+        /// Matching attestations needed to settle a transfer (0 while no relayers are set)
+        relayer_threshold: u32,
+        /// Outstanding attestations per transfer
+        attestations: Mapping<u64, Vec<Attestation>>,
+        /// Donated funds owed per transfer until it is confirmed or refunded
+        escrow: Mapping<u64, Balance>,
+        /// Sum of all escrowed funds
+        total_escrowed: Balance,
+        /// Escrowed funds still in the contract's custody per token, which
+        /// recovery must leave untouched
+        token_escrow: Mapping<AccountId, Balance>,
+        /// Donations received from other parachains
+        inbound_donations: Mapping<u64, InboundDonation>,
+        /// Inbound donation nonce for unique IDs
+        inbound_nonce: u64,
+        /// Inbound donation ID per source-chain reference
+        inbound_references: Mapping<Hash, u64>,
+        /// Outstanding relayer reports per source-chain reference
+        inbound_attestations: Mapping<Hash, Vec<InboundAttestation>>,
+        /// Account the XCM executor dispatches inbound credits from, if any
+        inbound_executor: Option<AccountId>,
+        /// Credited inbound donations not yet sent on, per (builder, asset)
+        inbound_claimable: Mapping<(u32, u32), Balance>,
+        /// Credited inbound funds in the contract's AssetHub balance per
+        /// token, which outbound transfers and recovery must leave untouched
+        inbound_reserved: Mapping<AccountId, Balance>,
+        /// Payout preferences per builder (defaults to the builder's own parachain and USDC)
+        payout_preferences: Mapping<u32, PayoutPreferences>,
+        /// Donations awaiting a batched payout, per builder
+        pending_payouts: Mapping<u32, PendingPayout>,
     }
 
     impl CrossChainDonation {
         /// Creates a new cross-chain donation contract
+        ///
+        /// `xcm_reserve` funds the AssetHub balance that PSP22-wrapped
+        /// donations are sent from (see `set_xcm_reserve`).
         #[ink(constructor)]
-        pub fn new(asset_hub_id: u32, usdc_asset_id: u32) -> Self {
+        pub fn new(asset_hub_id: u32, usdc_asset_id: u32, usdc_token: AccountId, xcm_reserve: AccountId) -> Self {
             let mut contract = Self {
-                owner: Self::env().caller(),
+                owner: AccountId::from(evm_interop::to_account(Self::env().caller())),
                 asset_hub_id,
                 usdc_asset_id,
+                xcm_reserve,
                 builders: Mapping::new(),
                 builder_count: 0,
                 transfers: Mapping::new(),
                 transfer_nonce: 0,
-                supported_assets: Mapping::new(),
+                idempotency_keys: Mapping::new(),
+                assets: Mapping::new(),
+                parachains: Mapping::new(),
+                parachain_ids: Vec::new(),
                 paused: false,
+                transfer_timeout: DEFAULT_TRANSFER_TIMEOUT,
+                relayers: Vec::new(),
+                relayer_threshold: 0,
+                attestations: Mapping::new(),
+                escrow: Mapping::new(),
+                total_escrowed: 0,
+                token_escrow: Mapping::new(),
+                inbound_donations: Mapping::new(),
+                inbound_nonce: 0,
+                inbound_references: Mapping::new(),
+                inbound_attestations: Mapping::new(),
+                inbound_executor: None,
+                inbound_claimable: Mapping::new(),
+                inbound_reserved: Mapping::new(),
+                payout_preferences: Mapping::new(),
+                pending_payouts: Mapping::new(),
             };
 
             // Add USDC as supported asset
-            contract.supported_assets.insert(usdc_asset_id, &true);
+            contract.assets.insert(usdc_asset_id, &AssetConfig {
+                token: usdc_token,
+                decimals: 6,
+                min_donation: USDC_MIN_DONATION,
+                max_donation: USDC_MAX_DONATION,
+                fee_asset_id: usdc_asset_id,
+                enabled: true,
+            });
 
-            // Initialize with demo builders
+            // Initialize with demo parachains and builders
+            contract.init_demo_parachains();
             contract.init_demo_builders();
 
             contract
         }
 
+        /// Initialize the registry with well-known parachains.
+        ///
+        /// The demo builders use generic-prefix (`5...`) dev addresses, so
+        /// these formats admit them on top of each chain's own prefix.
+        fn init_demo_parachains(&mut self) {
+            let parachains = vec![
+                (1000, "AssetHub"),
+                (2000, "Acala"),
+                (2004, "Moonbeam"),
+                (2006, "Astar"),
+                (2034, "Hydration"),
+            ];
+
+            for (para_id, name) in parachains {
+                let config = ParachainConfig {
+                    name: String::from(name),
+                    account_format: ss58::known_format(para_id)
+                        .expect("demo parachains have known account formats")
+                        .with_generic_prefix(),
+                    xcm_version: DEFAULT_XCM_VERSION,
+                    weight_limit: None,
+                    fee_budget: None,
+                    unit_weight: DEFAULT_UNIT_WEIGHT,
+                    fee_per_second: DEFAULT_FEE_PER_SECOND,
+                    enabled: true,
+                };
+                self.parachains.insert(para_id, &config);
+                self.parachain_ids.push(para_id);
+            }
+        }
+
         /// Initialize demo parachain builders
         fn init_demo_builders(&mut self) {
             let builders = vec![
                 (
                     "Alice - Moonbeam",
                     "EVM Smart Contracts for Polkadot",
-                    "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+                    "0xf24FF3a9CF04c71Dbc94D0b566f7A27B94566cac", // Alith (AccountKey20)
                     2004, // Moonbeam parachain ID
                 ),
                 (
@@ -156,334 +685,3144 @@ pub mod cross_chain {
             ];
 
             for (name, project, address, para_id) in builders {
+                let beneficiary = ss58::parse_beneficiary(address, self.account_format(para_id))
+                    .expect("demo builder addresses are valid");
+
                 self.builder_count += 1;
                 let builder = ParachainBuilder {
                     name: String::from(name),
                     project: String::from(project),
                     substrate_address: String::from(address),
+                    beneficiary,
                     parachain_id: para_id,
                     total_received: 0,
                     active: true,
+                    controller: None,
                 };
                 self.builders.insert(self.builder_count, &builder);
             }
         }
 
-        /// Donate to a parachain builder (triggers XCM transfer)
+        /// Donate to a parachain builder in their preferred asset (USDC by default)
         #[ink(message)]
         pub fn donate(
             &mut self,
             builder_id: u32,
             amount: Balance,
+            idempotency_key: Option<Hash>,
+        ) -> Result<u64, CrossChainError> {
+            let asset_id = self.payout_preferences.get(builder_id)
+                .map_or(self.usdc_asset_id, |prefs| prefs.asset_id);
+            self.donate_asset(builder_id, asset_id, amount, idempotency_key)
+        }
+
+        /// Donate any enabled asset to a parachain builder (triggers XCM transfer)
+        ///
+        /// The donor must have approved this contract for `amount` on the asset's
+        /// token. A PSP22 token stays in escrow until the transfer is refunded,
+        /// or confirmed and paid to the XCM reserve whose AssetHub funds the
+        /// transfer spent. An asset held through its precompile is what the
+        /// XCM withdraws.
+        ///
+        /// Resubmitting with the same `idempotency_key` returns the original
+        /// transfer ID without donating again.
+        ///
+        /// Donations follow the builder's payout preferences. Those in the
+        /// preferred asset are queued for a batched payout when `min_batch` is set.
+        #[ink(message)]
+        pub fn donate_asset(
+            &mut self,
+            builder_id: u32,
+            asset_id: u32,
+            amount: Balance,
+            idempotency_key: Option<Hash>,
         ) -> Result<u64, CrossChainError> {
             self.ensure_not_paused()?;
 
-            if amount < 1_000_000 {
-                return Err(CrossChainError::InvalidAmount); // Min 1 USDC (6 decimals)
+            let donor = self.caller_account();
+            if let Some(key) = idempotency_key {
+                if let Some(transfer_id) = self.idempotency_keys.get((donor, key)) {
+                    let original = self.transfers.get(transfer_id)
+                        .ok_or(CrossChainError::TransferFailed)?;
+                    if original.builder_id != builder_id
+                        || original.asset_id != asset_id
+                        || original.amount != amount
+                    {
+                        return Err(CrossChainError::IdempotencyKeyReused);
+                    }
+                    return Ok(transfer_id);
+                }
             }
 
-            let mut builder = self.builders.get(builder_id)
-                .ok_or(CrossChainError::InvalidBuilder)?;
-
-            if !builder.active {
-                return Err(CrossChainError::BuilderNotActive);
-            }
+            let (asset, route) = self.validate_donation(builder_id, asset_id, amount)?;
 
             // Create XCM transfer record
             self.transfer_nonce += 1;
             let transfer_id = self.transfer_nonce;
 
             let transfer = XcmTransfer {
-                from: self.env().caller(),
-                to_substrate: builder.substrate_address.clone(),
-                parachain_id: builder.parachain_id,
-                asset_id: self.usdc_asset_id,
+                from: donor,
+                builder_id,
+                to_substrate: route.address.clone(),
+                beneficiary: route.beneficiary,
+                parachain_id: route.parachain_id,
+                asset_id,
+                token: asset.token,
                 amount,
                 timestamp: self.env().block_timestamp(),
+                sent_at: self.env().block_number(),
                 status: TransferStatus::Pending,
+                failure_reason: None,
+                dest_block_hash: None,
+                message_hash: None,
+                idempotency_key,
             };
 
             self.transfers.insert(transfer_id, &transfer);
+            if let Some(key) = idempotency_key {
+                self.idempotency_keys.insert((donor, key), &transfer_id);
+            }
 
-            // In production: Build and execute XCM message here
-            // For now, we simulate the transfer
-            self.execute_xcm_transfer(transfer_id)?;
-
-            // Update builder stats
-            builder.total_received += amount;
-            self.builders.insert(builder_id, &builder);
+            // Take custody of the donation before anything leaves the chain
+            self.pull_funds(asset.token, transfer.from, amount)?;
+            self.escrow.insert(transfer_id, &amount);
+            self.total_escrowed += amount;
+            let token_escrow = self.token_escrow.get(asset.token).unwrap_or(0);
+            self.token_escrow.insert(asset.token, &(token_escrow + amount));
 
-            // Emit events
-            self.env().emit_event(CrossChainDonation {
-                donor: self.env().caller(),
+            self.env().emit_event(DonationMade {
+                donor,
                 builder_id,
                 amount,
-                substrate_address: builder.substrate_address,
-                parachain_id: builder.parachain_id,
+                substrate_address: route.address.clone(),
+                parachain_id: route.parachain_id,
                 timestamp: self.env().block_timestamp(),
             });
 
-            self.env().emit_event(XcmTransferInitiated {
-                transfer_id,
-                from: self.env().caller(),
-                to: transfer.to_substrate,
-                amount,
-                parachain_id: builder.parachain_id,
-            });
+            // Build and execute the reserve-transfer XCM program, or queue the
+            // donation for a batched payout. Builder stats are only updated
+            // once the transfer is confirmed.
+            if asset_id == route.asset_id && route.min_batch > 0 {
+                let mut pending = self.pending_payouts.get(builder_id).unwrap_or_default();
+                pending.amount += amount;
+                pending.transfer_ids.push(transfer_id);
+                self.pending_payouts.insert(builder_id, &pending);
+
+                self.env().emit_event(PayoutQueued {
+                    builder_id,
+                    transfer_id,
+                    pending_amount: pending.amount,
+                });
+            } else {
+                self.send_payout(&route, PendingPayout { amount, transfer_ids: vec![transfer_id] })?;
+            }
 
             Ok(transfer_id)
         }
 
-        /// Simulate XCM transfer execution
-        fn execute_xcm_transfer(&mut self, transfer_id: u64) -> Result<(), CrossChainError> {
-            let mut transfer = self.transfers.get(transfer_id)
-                .ok_or(CrossChainError::TransferFailed)?;
+        /// Pay out a builder's pending batch in a single XCM transfer.
+        ///
+        /// The builder's controller and the owner can flush at any time; anyone
+        /// else (a keeper) only once the batch has reached `min_batch`.
+        #[ink(message)]
+        pub fn flush_payout(&mut self, builder_id: u32) -> Result<Hash, CrossChainError> {
+            self.ensure_not_paused()?;
 
-            // In production: Build XCM message
-            // let message = Xcm(vec![
-            //     WithdrawAsset(asset),
-            //     InitiateReserveWithdraw {
-            //         assets: All.into(),
-            //         reserve: parachain_location,
-            //         xcm: Xcm(vec![
-            //             BuyExecution { fees, weight_limit },
-            //             DepositAsset {
-            //                 assets: All.into(),
-            //                 beneficiary,
-            //             },
-            //         ]),
-            //     },
-            // ]);
-
-            // Simulate successful execution
-            transfer.status = TransferStatus::Sent;
-            self.transfers.insert(transfer_id, &transfer);
+            let builder = self.builders.get(builder_id)
+                .ok_or(CrossChainError::InvalidBuilder)?;
+            let pending = self.pending_payouts.get(builder_id)
+                .ok_or(CrossChainError::NothingToFlush)?;
+            let route = self.payout_route(builder_id, &builder);
+
+            let caller = self.caller_account();
+            let privileged = caller == self.owner || builder.controller == Some(caller);
+            if !privileged && pending.amount < route.min_batch {
+                return Err(CrossChainError::BatchBelowMinimum);
+            }
 
-            Ok(())
+            self.ensure_parachain_enabled(route.parachain_id)?;
+            self.flush_batch(builder_id, &route, pending)
         }
 
-        /// Confirm XCM transfer completion (called by relayer or oracle)
+        /// Get a builder's pending batched payout
         #[ink(message)]
-        pub fn confirm_transfer(&mut self, transfer_id: u64) -> Result<(), CrossChainError> {
-            self.ensure_owner()?;
+        pub fn get_pending_payout(&self, builder_id: u32) -> PendingPayout {
+            self.pending_payouts.get(builder_id).unwrap_or_default()
+        }
 
-            let mut transfer = self.transfers.get(transfer_id)
-                .ok_or(CrossChainError::TransferFailed)?;
+        /// Set where and how a builder is paid (owner or the builder's controller).
+        ///
+        /// `address` must match the account format of `parachain_id`. Pending
+        /// batches must be flushed first.
+        #[ink(message)]
+        pub fn set_payout_preferences(
+            &mut self,
+            builder_id: u32,
+            parachain_id: u32,
+            address: String,
+            asset_id: u32,
+            min_batch: Balance,
+        ) -> Result<(), CrossChainError> {
+            let builder = self.builders.get(builder_id)
+                .ok_or(CrossChainError::InvalidBuilder)?;
+            self.ensure_owner_or_controller(&builder)?;
 
-            transfer.status = TransferStatus::Confirmed;
-            self.transfers.insert(transfer_id, &transfer);
+            if self.pending_payouts.contains(builder_id) {
+                return Err(CrossChainError::PayoutPending);
+            }
+            if !self.is_asset_supported(asset_id) {
+                return Err(CrossChainError::AssetNotSupported);
+            }
+            self.ensure_parachain_enabled(parachain_id)?;
+            let beneficiary = ss58::parse_beneficiary(&address, self.account_format(parachain_id))?;
 
-            self.env().emit_event(XcmTransferCompleted {
-                transfer_id,
-                success: true,
+            self.env().emit_event(PayoutPreferencesUpdated {
+                builder_id,
+                parachain_id,
+                address: address.clone(),
+                asset_id,
+                min_batch,
+            });
+            self.payout_preferences.insert(builder_id, &PayoutPreferences {
+                parachain_id,
+                address,
+                beneficiary,
+                asset_id,
+                min_batch,
             });
-
             Ok(())
         }
 
-        /// Add a new parachain builder
+        /// Get a builder's effective payout preferences
         #[ink(message)]
-        pub fn add_builder(
+        pub fn get_payout_preferences(&self, builder_id: u32) -> Option<PayoutPreferences> {
+            self.builders.get(builder_id).map(|builder| self.payout_route(builder_id, &builder))
+        }
+
+        /// Set the local account that manages a builder's payouts (owner only)
+        #[ink(message)]
+        pub fn set_builder_controller(
             &mut self,
-            name: String,
-            project: String,
-            substrate_address: String,
-            parachain_id: u32,
-        ) -> Result<u32, CrossChainError> {
+            builder_id: u32,
+            controller: Option<AccountId>,
+        ) -> Result<(), CrossChainError> {
             self.ensure_owner()?;
-            self.ensure_not_paused()?;
+            let mut builder = self.builders.get(builder_id)
+                .ok_or(CrossChainError::InvalidBuilder)?;
+            builder.controller = controller;
+            self.builders.insert(builder_id, &builder);
+            Ok(())
+        }
+
+        /// Build and execute one reserve-transfer XCM program for a batch of
+        /// transfers, returning the hash of the message sent
+        fn send_payout(
+            &mut self,
+            route: &PayoutPreferences,
+            payout: PendingPayout,
+        ) -> Result<Hash, CrossChainError> {
+            let first = payout.transfer_ids.first()
+                .and_then(|id| self.transfers.get(id))
+                .ok_or(CrossChainError::NothingToFlush)?;
+
+            // The program withdraws from the contract's AssetHub balance and
+            // may only spend this batch's own precompile escrow, never credited
+            // inbound funds or other donors' escrow
+            let holding = self.get_assets_precompile(first.asset_id);
+            let own_escrow = if Self::is_assets_precompile(&first.token) { payout.amount } else { 0 };
+            if payout.amount > self.unreserved_balance(holding).saturating_add(own_escrow) {
+                return Err(CrossChainError::FundsReserved);
+            }
+
+            let message = self.build_message(route.parachain_id, first.asset_id, payout.amount, route.beneficiary)?;
+            self.submit_xcm(&message)?;
+            let message_hash = Self::message_hash(&message);
+
+            for &transfer_id in &payout.transfer_ids {
+                let mut transfer = self.transition(transfer_id, TransferStatus::Sent)?;
+
+                // The program withdrew the asset from the contract's own
+                // balance. For donations escrowed through the assets
+                // precompile that balance is the escrow itself, which has
+                // now left and is no longer held for the transfer.
+                if Self::is_assets_precompile(&transfer.token) {
+                    let amount = self.release_escrow(transfer_id);
+                    self.release_custody(transfer.token, amount);
+                }
+
+                transfer.sent_at = self.env().block_number();
+                transfer.message_hash = Some(message_hash);
+                self.transfers.insert(transfer_id, &transfer);
+
+                self.env().emit_event(XcmTransferInitiated {
+                    transfer_id,
+                    from: transfer.from,
+                    to: transfer.to_substrate,
+                    amount: transfer.amount,
+                    parachain_id: transfer.parachain_id,
+                    message_hash,
+                });
+            }
+
+            Ok(message_hash)
+        }
+
+        /// Send a builder's batch and clear it
+        fn flush_batch(
+            &mut self,
+            builder_id: u32,
+            route: &PayoutPreferences,
+            pending: PendingPayout,
+        ) -> Result<Hash, CrossChainError> {
+            self.pending_payouts.remove(builder_id);
+
+            let amount = pending.amount;
+            let transfer_count = pending.transfer_ids.len() as u32;
+            let message_hash = self.send_payout(route, pending)?;
+
+            self.env().emit_event(PayoutFlushed {
+                builder_id,
+                asset_id: route.asset_id,
+                amount,
+                transfer_count,
+                message_hash,
+            });
+
+            Ok(message_hash)
+        }
+
+        /// Blake2-256 hash of a SCALE-encoded XCM program
+        fn message_hash(message: &VersionedXcm<()>) -> Hash {
+            let mut output = <Blake2x256 as HashOutput>::Type::default();
+            ink::env::hash_encoded::<Blake2x256, _>(message, &mut output);
+            Hash::from(output)
+        }
+
+        /// Estimate the weight, fees and net amount of a donation.
+        ///
+        /// Builds the same XCM program `donate_asset` would send and prices each
+        /// remote hop with that parachain's fee parameters.
+        #[ink(message)]
+        pub fn estimate_donation(
+            &self,
+            builder_id: u32,
+            asset_id: u32,
+            amount: Balance,
+        ) -> Result<DonationEstimate, CrossChainError> {
+            let (asset, route) = self.validate_donation(builder_id, asset_id, amount)?;
+            let dest = self.parachains.get(route.parachain_id)
+                .ok_or(CrossChainError::InvalidParachain)?;
+
+            let program = self
+                .reserve_transfer(&dest, route.parachain_id, asset_id, amount, route.beneficiary)
+                .program();
+
+            let mut weight = XcmWeight { ref_time: 0, proof_size: 0 };
+            let mut fee: Balance = 0;
+            for (para_id, instructions) in reserve_transfer::remote_instruction_counts(&program) {
+                let hop = self.parachains.get(para_id)
+                    .ok_or(CrossChainError::InvalidParachain)?;
+
+                let ref_time = hop.unit_weight.ref_time.saturating_mul(instructions as u64);
+                let hop_fee = (ref_time as u128)
+                    .saturating_mul(hop.fee_per_second)
+                    .div_ceil(REF_TIME_PER_SECOND);
+
+                // The final hop pays from the destination's fee budget
+                if para_id == route.parachain_id && dest.fee_budget.is_some_and(|budget| hop_fee > budget) {
+                    return Err(CrossChainError::FeeBudgetExceeded);
+                }
+
+                weight.ref_time = weight.ref_time.saturating_add(ref_time);
+                weight.proof_size = weight.proof_size
+                    .saturating_add(hop.unit_weight.proof_size.saturating_mul(instructions as u64));
+                fee = fee.saturating_add(hop_fee);
+            }
+
+            // Fees come out of the donation only when paid in the donated asset
+            let net_amount = if asset.fee_asset_id == asset_id {
+                amount.checked_sub(fee).filter(|net| *net > 0).ok_or(CrossChainError::InvalidAmount)?
+            } else {
+                amount
+            };
+
+            Ok(DonationEstimate {
+                weight,
+                fee_asset_id: asset.fee_asset_id,
+                fee,
+                net_amount,
+            })
+        }
+
+        /// Build the versioned XCM program that delivers a transfer to its builder
+        #[cfg(test)]
+        fn build_transfer_message(&self, transfer: &XcmTransfer) -> Result<VersionedXcm<()>, CrossChainError> {
+            self.build_message(transfer.parachain_id, transfer.asset_id, transfer.amount, transfer.beneficiary)
+        }
+
+        /// Build the versioned XCM program that delivers `amount` to `beneficiary`
+        fn build_message(
+            &self,
+            para_id: u32,
+            asset_id: u32,
+            amount: Balance,
+            beneficiary: Beneficiary,
+        ) -> Result<VersionedXcm<()>, CrossChainError> {
+            let parachain = self.parachains.get(para_id)
+                .ok_or(CrossChainError::InvalidParachain)?;
+
+            self.reserve_transfer(&parachain, para_id, asset_id, amount, beneficiary)
+                .build()
+                .into_version(parachain.xcm_version)
+                .map_err(|_| CrossChainError::UnsupportedXcmVersion)
+        }
+
+        /// Helper: reserve transfer parameters for a donation to `parachain`
+        fn reserve_transfer(
+            &self,
+            parachain: &ParachainConfig,
+            para_id: u32,
+            asset_id: u32,
+            amount: Balance,
+            beneficiary: Beneficiary,
+        ) -> ReserveTransfer {
+            ReserveTransfer {
+                asset_hub_id: self.asset_hub_id,
+                asset_id,
+                amount,
+                dest_para_id: para_id,
+                beneficiary,
+                fee_budget: parachain.fee_budget,
+                weight_limit: parachain.weight_limit,
+            }
+        }
+
+        /// Helper: a builder's payout preferences, defaulting to its own parachain and USDC
+        fn payout_route(&self, builder_id: u32, builder: &ParachainBuilder) -> PayoutPreferences {
+            self.payout_preferences.get(builder_id).unwrap_or_else(|| PayoutPreferences {
+                parachain_id: builder.parachain_id,
+                address: builder.substrate_address.clone(),
+                beneficiary: builder.beneficiary,
+                asset_id: self.usdc_asset_id,
+                min_batch: 0,
+            })
+        }
+
+        /// Helper: check a donation against asset, builder and parachain state,
+        /// returning the asset config and the route it takes
+        fn validate_donation(
+            &self,
+            builder_id: u32,
+            asset_id: u32,
+            amount: Balance,
+        ) -> Result<(AssetConfig, PayoutPreferences), CrossChainError> {
+            let asset = self.assets.get(asset_id)
+                .filter(|asset| asset.enabled)
+                .ok_or(CrossChainError::AssetNotSupported)?;
+
+            if amount < asset.min_donation || amount > asset.max_donation {
+                return Err(CrossChainError::InvalidAmount);
+            }
+
+            let builder = self.builders.get(builder_id)
+                .ok_or(CrossChainError::InvalidBuilder)?;
+
+            if !builder.active {
+                return Err(CrossChainError::BuilderNotActive);
+            }
+
+            let route = self.payout_route(builder_id, &builder);
+            self.ensure_parachain_enabled(route.parachain_id)?;
+
+            Ok((asset, route))
+        }
+
+        /// Execute an XCM program locally through the runtime
+        #[cfg(not(test))]
+        fn submit_xcm(&self, message: &VersionedXcm<()>) -> Result<(), CrossChainError> {
+            self.env()
+                .xcm_execute(message)
+                .map_err(|_| CrossChainError::XcmExecutionFailed)
+        }
+
+        /// Off-chain the program runs against the test token ledger
+        #[cfg(test)]
+        fn submit_xcm(&self, message: &VersionedXcm<()>) -> Result<(), CrossChainError> {
+            tests::execute_xcm(self.own_account(), message)
+        }
+
+        /// Confirm XCM transfer completion.
+        ///
+        /// Owner fallback while no relayers are configured; afterwards
+        /// transfers settle through `attest_transfer`.
+        #[ink(message)]
+        pub fn confirm_transfer(&mut self, transfer_id: u64) -> Result<(), CrossChainError> {
+            self.ensure_owner_settlement()?;
+            self.settle_delivered(transfer_id, None)
+        }
+
+        /// Report that an XCM transfer failed on the destination.
+        ///
+        /// Owner fallback while no relayers are configured; afterwards
+        /// transfers settle through `attest_transfer`.
+        #[ink(message)]
+        pub fn report_failure(&mut self, transfer_id: u64, reason: String) -> Result<(), CrossChainError> {
+            self.ensure_owner_settlement()?;
+            self.settle_failed(transfer_id, reason, None)
+        }
+
+        /// Attest the outcome of a transfer on its destination chain (relayers only).
+        ///
+        /// The transfer settles once `relayer_threshold` relayers agree on the
+        /// same outcome and block hash. Attestations that disagree with an
+        /// earlier one emit `AttestationDisputed`.
+        #[ink(message)]
+        pub fn attest_transfer(
+            &mut self,
+            transfer_id: u64,
+            outcome: TransferOutcome,
+            dest_block_hash: Hash,
+        ) -> Result<(), CrossChainError> {
+            let relayer = self.caller_account();
+            if !self.relayers.contains(&relayer) {
+                return Err(CrossChainError::NotRelayer);
+            }
+
+            let transfer = self.transfers.get(transfer_id)
+                .ok_or(CrossChainError::TransferFailed)?;
+            let target = match outcome {
+                TransferOutcome::Delivered => TransferStatus::Confirmed,
+                TransferOutcome::Failed => TransferStatus::Failed,
+            };
+            if !transfer.status.can_transition_to(target) {
+                return Err(CrossChainError::InvalidTransition);
+            }
+
+            // Attestations from removed relayers are dropped, so they neither
+            // count nor raise disputes
+            let mut attestations = self.attestations.get(transfer_id).unwrap_or_default();
+            attestations.retain(|a| self.relayers.contains(&a.relayer));
+            if attestations.iter().any(|a| a.relayer == relayer) {
+                return Err(CrossChainError::AlreadyAttested);
+            }
+
+            let disputed = attestations
+                .iter()
+                .any(|a| a.outcome != outcome || a.dest_block_hash != dest_block_hash);
+
+            attestations.push(Attestation { relayer, outcome, dest_block_hash });
+
+            self.env().emit_event(TransferAttested {
+                transfer_id,
+                relayer,
+                outcome,
+                dest_block_hash,
+            });
+            if disputed {
+                self.env().emit_event(AttestationDisputed {
+                    transfer_id,
+                    relayer,
+                    outcome,
+                    dest_block_hash,
+                });
+            }
+
+            let agreeing = attestations
+                .iter()
+                .filter(|a| a.outcome == outcome && a.dest_block_hash == dest_block_hash)
+                .count() as u32;
+
+            if agreeing < self.relayer_threshold {
+                self.attestations.insert(transfer_id, &attestations);
+                return Ok(());
+            }
+
+            self.attestations.remove(transfer_id);
+            match outcome {
+                TransferOutcome::Delivered => self.settle_delivered(transfer_id, Some(dest_block_hash)),
+                TransferOutcome::Failed => self.settle_failed(
+                    transfer_id,
+                    String::from("Attested by relayers"),
+                    Some(dest_block_hash),
+                ),
+            }
+        }
+
+        /// Get outstanding attestations from current relayers for a transfer
+        #[ink(message)]
+        pub fn get_attestations(&self, transfer_id: u64) -> Vec<Attestation> {
+            let mut attestations = self.attestations.get(transfer_id).unwrap_or_default();
+            attestations.retain(|a| self.relayers.contains(&a.relayer));
+            attestations
+        }
+
+        /// Credit a supported asset sent from another parachain to the
+        /// contract's sovereign location with a builder memo.
+        ///
+        /// The configured XCM executor account credits directly. Relayers
+        /// attest instead, and the donation is credited once
+        /// `relayer_threshold` current relayers report the same origin,
+        /// builder, asset and amount for `reference`. Returns the inbound
+        /// donation ID once credited. Each source-chain `reference` is
+        /// credited at most once.
+        ///
+        /// Credited funds stay reserved in the contract's AssetHub balance
+        /// until `claim_inbound` sends them on to the builder.
+        #[ink(message)]
+        pub fn credit_inbound(
+            &mut self,
+            origin: InboundOrigin,
+            asset_id: u32,
+            amount: Balance,
+            builder_id: u32,
+            reference: Hash,
+        ) -> Result<Option<u64>, CrossChainError> {
+            self.ensure_not_paused()?;
+
+            let caller = self.caller_account();
+            let is_executor = self.inbound_executor == Some(caller);
+            if !is_executor && !self.relayers.contains(&caller) {
+                return Err(CrossChainError::Unauthorized);
+            }
+
+            if self.inbound_references.contains(reference) {
+                return Err(CrossChainError::DuplicateReference);
+            }
+
+            if amount == 0 {
+                return Err(CrossChainError::InvalidAmount);
+            }
+
+            if !self.is_asset_supported(asset_id) {
+                return Err(CrossChainError::AssetNotSupported);
+            }
+
+            let mut builder = self.builders.get(builder_id)
+                .ok_or(CrossChainError::InvalidBuilder)?;
+
+            if !builder.active {
+                return Err(CrossChainError::BuilderNotActive);
+            }
+
+            if !is_executor {
+                let attestation = InboundAttestation { relayer: caller, origin, builder_id, asset_id, amount };
+                if !self.attest_inbound(reference, attestation)? {
+                    return Ok(None);
+                }
+            }
+
+            self.inbound_nonce += 1;
+            let inbound_id = self.inbound_nonce;
+
+            let donation = InboundDonation {
+                origin,
+                builder_id,
+                asset_id,
+                amount,
+                reference,
+                credited_by: caller,
+                timestamp: self.env().block_timestamp(),
+            };
+            self.inbound_donations.insert(inbound_id, &donation);
+            self.inbound_references.insert(reference, &inbound_id);
+
+            builder.total_received += amount;
+            self.builders.insert(builder_id, &builder);
+
+            let claimable = self.inbound_claimable.get((builder_id, asset_id)).unwrap_or(0);
+            self.inbound_claimable.insert((builder_id, asset_id), &(claimable + amount));
+            let holding = self.get_assets_precompile(asset_id);
+            let reserved = self.inbound_reserved.get(holding).unwrap_or(0);
+            self.inbound_reserved.insert(holding, &(reserved + amount));
+
+            // Relayer reports that raced the executor are settled too
+            self.inbound_attestations.remove(reference);
+
+            self.env().emit_event(InboundDonationCredited {
+                inbound_id,
+                reference,
+                builder_id,
+                origin_para_id: origin.para_id,
+                origin_account: origin.account,
+                asset_id,
+                amount,
+            });
+
+            Ok(Some(inbound_id))
+        }
+
+        /// Helper: record a relayer's report of an inbound donation, returning
+        /// whether enough current relayers now agree on it
+        fn attest_inbound(&mut self, reference: Hash, attestation: InboundAttestation) -> Result<bool, CrossChainError> {
+            let mut attestations = self.inbound_attestations.get(reference).unwrap_or_default();
+            attestations.retain(|a| self.relayers.contains(&a.relayer));
+            if attestations.iter().any(|a| a.relayer == attestation.relayer) {
+                return Err(CrossChainError::AlreadyAttested);
+            }
+
+            self.env().emit_event(InboundAttested {
+                reference,
+                relayer: attestation.relayer,
+                builder_id: attestation.builder_id,
+                asset_id: attestation.asset_id,
+                amount: attestation.amount,
+            });
+
+            // The new report agrees with itself
+            let agreeing = 1 + attestations.iter().filter(|a| a.agrees_with(&attestation)).count() as u32;
+            attestations.push(attestation);
+
+            if agreeing < self.relayer_threshold {
+                self.inbound_attestations.insert(reference, &attestations);
+                return Ok(false);
+            }
+
+            self.inbound_attestations.remove(reference);
+            Ok(true)
+        }
+
+        /// Get outstanding relayer reports from current relayers for an inbound reference
+        #[ink(message)]
+        pub fn get_inbound_attestations(&self, reference: Hash) -> Vec<InboundAttestation> {
+            let mut attestations = self.inbound_attestations.get(reference).unwrap_or_default();
+            attestations.retain(|a| self.relayers.contains(&a.relayer));
+            attestations
+        }
+
+        /// Get an inbound donation
+        #[ink(message)]
+        pub fn get_inbound_donation(&self, inbound_id: u64) -> Option<InboundDonation> {
+            self.inbound_donations.get(inbound_id)
+        }
+
+        /// Get the inbound donation credited for a source-chain reference
+        #[ink(message)]
+        pub fn get_inbound_by_reference(&self, reference: Hash) -> Option<u64> {
+            self.inbound_references.get(reference)
+        }
+
+        /// Send a builder's credited inbound donations in `asset_id` to its
+        /// payout destination in a single XCM transfer, returning the hash of
+        /// the message sent.
+        ///
+        /// Anyone can call this, since the funds only go where the builder's
+        /// payout preferences point.
+        #[ink(message)]
+        pub fn claim_inbound(&mut self, builder_id: u32, asset_id: u32) -> Result<Hash, CrossChainError> {
+            self.ensure_not_paused()?;
+
+            let builder = self.builders.get(builder_id)
+                .ok_or(CrossChainError::InvalidBuilder)?;
+            let amount = self.inbound_claimable.get((builder_id, asset_id))
+                .ok_or(CrossChainError::NothingToClaim)?;
+            let route = self.payout_route(builder_id, &builder);
+            self.ensure_parachain_enabled(route.parachain_id)?;
+
+            // Release the reservation the program is about to spend
+            self.inbound_claimable.remove((builder_id, asset_id));
+            let holding = self.get_assets_precompile(asset_id);
+            let reserved = self.inbound_reserved.get(holding).unwrap_or(0);
+            self.inbound_reserved.insert(holding, &reserved.saturating_sub(amount));
+
+            let message = self.build_message(route.parachain_id, asset_id, amount, route.beneficiary)?;
+            self.submit_xcm(&message)?;
+            let message_hash = Self::message_hash(&message);
+
+            self.env().emit_event(InboundClaimed {
+                builder_id,
+                asset_id,
+                amount,
+                parachain_id: route.parachain_id,
+                message_hash,
+            });
+
+            Ok(message_hash)
+        }
+
+        /// Get a builder's credited inbound donations in an asset that are not yet claimed
+        #[ink(message)]
+        pub fn get_inbound_claimable(&self, builder_id: u32, asset_id: u32) -> Balance {
+            self.inbound_claimable.get((builder_id, asset_id)).unwrap_or(0)
+        }
+
+        /// Set the account the XCM executor credits inbound donations from (owner only)
+        #[ink(message)]
+        pub fn set_inbound_executor(&mut self, executor: Option<AccountId>) -> Result<(), CrossChainError> {
+            self.ensure_owner()?;
+            self.inbound_executor = executor;
+            self.env().emit_event(InboundExecutorChanged { executor });
+            Ok(())
+        }
+
+        /// Add a relayer (owner only).
+        ///
+        /// Raises the threshold to a majority of the new set if it fell
+        /// below one, so the first relayer sets it to 1.
+        #[ink(message)]
+        pub fn add_relayer(&mut self, relayer: AccountId) -> Result<(), CrossChainError> {
+            self.ensure_owner()?;
+            if self.relayers.contains(&relayer) {
+                return Err(CrossChainError::RelayerAlreadyExists);
+            }
+
+            self.relayers.push(relayer);
+            self.env().emit_event(RelayerAdded { relayer });
+
+            let majority = Self::majority(self.relayers.len());
+            if self.relayer_threshold < majority {
+                self.update_threshold(majority);
+            }
+            Ok(())
+        }
+
+        /// Remove a relayer (owner only).
+        ///
+        /// Lowers the threshold to the remaining set's size if it could no
+        /// longer be reached; removing the last relayer returns settlement
+        /// to the owner. Attestations the relayer already made stop counting.
+        #[ink(message)]
+        pub fn remove_relayer(&mut self, relayer: AccountId) -> Result<(), CrossChainError> {
+            self.ensure_owner()?;
+            let index = self.relayers.iter().position(|r| *r == relayer)
+                .ok_or(CrossChainError::NotRelayer)?;
+
+            self.relayers.swap_remove(index);
+            self.env().emit_event(RelayerRemoved { relayer });
+
+            let remaining = self.relayers.len() as u32;
+            if self.relayer_threshold > remaining {
+                self.update_threshold(remaining);
+            }
+            Ok(())
+        }
+
+        /// Set how many matching attestations settle a transfer (owner only).
+        ///
+        /// Must be a strict majority of the relayer set, so two conflicting
+        /// outcomes can never both settle.
+        #[ink(message)]
+        pub fn set_relayer_threshold(&mut self, threshold: u32) -> Result<(), CrossChainError> {
+            self.ensure_owner()?;
+            if threshold < Self::majority(self.relayers.len()) || threshold as usize > self.relayers.len() {
+                return Err(CrossChainError::InvalidThreshold);
+            }
+            self.update_threshold(threshold);
+            Ok(())
+        }
+
+        /// Set the XCM reserve (owner only).
+        ///
+        /// XCM transfers always withdraw from the contract's AssetHub
+        /// balance, which the reserve keeps funded for assets donated through
+        /// a PSP22 wrapper. Each such donation's escrow is paid to the reserve
+        /// once delivered; escrow already paid out stays where it went.
+        #[ink(message)]
+        pub fn set_xcm_reserve(&mut self, reserve: AccountId) -> Result<(), CrossChainError> {
+            self.ensure_owner()?;
+            self.xcm_reserve = reserve;
+            self.env().emit_event(XcmReserveChanged { reserve });
+            Ok(())
+        }
+
+        /// Get the XCM reserve
+        #[ink(message)]
+        pub fn get_xcm_reserve(&self) -> AccountId {
+            self.xcm_reserve
+        }
+
+        /// Get the relayer set and threshold
+        #[ink(message)]
+        pub fn get_relayers(&self) -> (Vec<AccountId>, u32) {
+            (self.relayers.clone(), self.relayer_threshold)
+        }
+
+        /// Helper: settle a transfer as delivered
+        fn settle_delivered(&mut self, transfer_id: u64, dest_block_hash: Option<Hash>) -> Result<(), CrossChainError> {
+            let mut transfer = self.transition(transfer_id, TransferStatus::Confirmed)?;
+            transfer.dest_block_hash = dest_block_hash;
+            self.transfers.insert(transfer_id, &transfer);
+
+            // PSP22 escrow reimburses the reserve for the AssetHub balance
+            // the XCM spent, instead of lingering where `recover_tokens`
+            // could sweep it
+            let amount = self.release_escrow(transfer_id);
+            if !Self::is_assets_precompile(&transfer.token) {
+                self.release_custody(transfer.token, amount);
+                self.send_funds(transfer.token, self.xcm_reserve, amount)?;
+            }
+
+            // Only delivered funds count towards the builder's total
+            if let Some(mut builder) = self.builders.get(transfer.builder_id) {
+                builder.total_received += transfer.amount;
+                self.builders.insert(transfer.builder_id, &builder);
+            }
+
+            self.env().emit_event(XcmTransferCompleted {
+                transfer_id,
+                success: true,
+            });
+
+            Ok(())
+        }
+
+        /// Helper: settle a transfer as failed
+        fn settle_failed(
+            &mut self,
+            transfer_id: u64,
+            reason: String,
+            dest_block_hash: Option<Hash>,
+        ) -> Result<(), CrossChainError> {
+            let mut transfer = self.transition(transfer_id, TransferStatus::Failed)?;
+            transfer.failure_reason = Some(reason.clone());
+            transfer.dest_block_hash = dest_block_hash;
+            self.transfers.insert(transfer_id, &transfer);
+
+            self.env().emit_event(XcmTransferFailed { transfer_id, reason });
+            self.env().emit_event(XcmTransferCompleted {
+                transfer_id,
+                success: false,
+            });
+
+            Ok(())
+        }
+
+        /// Helper: drop a transfer's escrow entry, returning the amount released
+        fn release_escrow(&mut self, transfer_id: u64) -> Balance {
+            let amount = self.escrow.take(transfer_id).unwrap_or(0);
+            self.total_escrowed -= amount;
+            amount
+        }
+
+        /// Helper: stop reserving `amount` of `token` that left the contract's custody
+        fn release_custody(&mut self, token: AccountId, amount: Balance) {
+            let token_escrow = self.token_escrow.get(token).unwrap_or(0);
+            self.token_escrow.insert(token, &token_escrow.saturating_sub(amount));
+        }
+
+        /// Helper: the contract's balance of `token` not reserved for escrow
+        /// or credited inbound donations
+        fn unreserved_balance(&self, token: AccountId) -> Balance {
+            let reserved = self.token_escrow.get(token).unwrap_or(0)
+                .saturating_add(self.inbound_reserved.get(token).unwrap_or(0));
+            self.token_balance(token).saturating_sub(reserved)
+        }
+
+        /// Pull a donation from the donor into contract custody
+        fn pull_funds(&self, token: AccountId, from: AccountId, amount: Balance) -> Result<(), CrossChainError> {
+            if Self::is_assets_precompile(&token) {
+                let args = assets_precompile::transfer_from_args(from.as_ref(), self.own_account().as_ref(), amount);
+                return self.precompile_transfer(token, assets_precompile::TRANSFER_FROM, args);
+            }
+            self.psp22_transfer_from(token, from, amount)
+        }
+
+        /// Send escrowed funds out of contract custody
+        fn send_funds(&self, token: AccountId, to: AccountId, amount: Balance) -> Result<(), CrossChainError> {
+            if Self::is_assets_precompile(&token) {
+                let args = assets_precompile::transfer_args(to.as_ref(), amount);
+                return self.precompile_transfer(token, assets_precompile::TRANSFER, args);
+            }
+            self.psp22_transfer(token, to, amount)
+        }
+
+        /// The contract's own balance of a token
+        fn token_balance(&self, token: AccountId) -> Balance {
+            if Self::is_assets_precompile(&token) {
+                let args = assets_precompile::address_word(self.own_account().as_ref()).to_vec();
+                return self.call_assets_precompile(token, assets_precompile::BALANCE_OF, args)
+                    .and_then(|output| assets_precompile::decode_uint(&output))
+                    .unwrap_or(0);
+            }
+            self.psp22_balance(token)
+        }
+
+        /// Helper: move funds with an assets precompile's `transfer` or `transferFrom`
+        fn precompile_transfer(&self, precompile: AccountId, selector: [u8; 4], args: Vec<u8>) -> Result<(), CrossChainError> {
+            self.call_assets_precompile(precompile, selector, args)
+                .filter(|output| assets_precompile::is_success(output))
+                .map(|_| ())
+                .ok_or(CrossChainError::TokenTransferFailed)
+        }
+
+        /// Call the ERC20 interface of an assets precompile
+        ///
+        /// `selector` and the ABI-encoded `args` go out verbatim; returns the
+        /// output untouched, or `None` if the call reverted.
+        #[cfg(not(test))]
+        fn call_assets_precompile(&self, precompile: AccountId, selector: [u8; 4], args: Vec<u8>) -> Option<Vec<u8>> {
+            let calldata = [&selector[..], &args].concat();
+            evm_interop::raw_call(Self::eth_address(&precompile), &calldata).ok()
+        }
+
+        #[cfg(not(test))]
+        fn psp22_transfer_from(&self, token: AccountId, from: AccountId, amount: Balance) -> Result<(), CrossChainError> {
+            use psp22_usdc::PSP22;
+
+            let mut token = Self::psp22(&token);
+            token.transfer_from(Self::eth_address(&from), self.env().address(), amount, Vec::new())
+                .map_err(|_| CrossChainError::TokenTransferFailed)
+        }
+
+        #[cfg(not(test))]
+        fn psp22_transfer(&self, token: AccountId, to: AccountId, amount: Balance) -> Result<(), CrossChainError> {
+            use psp22_usdc::PSP22;
+
+            let mut token = Self::psp22(&token);
+            token.transfer(Self::eth_address(&to), amount, Vec::new())
+                .map_err(|_| CrossChainError::TokenTransferFailed)
+        }
+
+        #[cfg(not(test))]
+        fn psp22_balance(&self, token: AccountId) -> Balance {
+            use psp22_usdc::PSP22;
+
+            Self::psp22(&token).balance_of(self.env().address())
+        }
+
+        /// PSP22 interface of a token contract
+        #[cfg(not(test))]
+        fn psp22(token: &AccountId) -> ink::contract_ref!(psp22_usdc::PSP22) {
+            Self::eth_address(token).into()
+        }
+
+        /// Address the H160 token sees for `account` (pallet-revive's account mapping)
+        #[cfg(not(test))]
+        fn eth_address(account: &AccountId) -> ink::primitives::H160 {
+            evm_interop::to_address(account.as_ref())
+        }
+
+        /// Account the caller's H160 maps to, which keys donors, relayers and controllers
+        fn caller_account(&self) -> AccountId {
+            AccountId::from(evm_interop::to_account(self.env().caller()))
+        }
+
+        /// This contract's account, as pallet-revive maps its address
+        fn own_account(&self) -> AccountId {
+            AccountId::from(evm_interop::to_account(self.env().address()))
+        }
+
+        /// Check whether `token` is a pallet-assets precompile
+        fn is_assets_precompile(token: &AccountId) -> bool {
+            assets_precompile::asset_id(token.as_ref()).is_some()
+        }
+
+        /// Off-chain precompiles answer from the test ledger in raw ABI
+        #[cfg(test)]
+        fn call_assets_precompile(&self, precompile: AccountId, selector: [u8; 4], args: Vec<u8>) -> Option<Vec<u8>> {
+            tests::call_assets_precompile(self.own_account(), precompile, selector, &args).ok()
+        }
+
+        /// Off-chain PSP22 tokens move on the test ledger
+        #[cfg(test)]
+        fn psp22_transfer_from(&self, token: AccountId, from: AccountId, amount: Balance) -> Result<(), CrossChainError> {
+            tests::move_tokens(token, from, self.own_account(), amount)
+        }
+
+        #[cfg(test)]
+        fn psp22_transfer(&self, token: AccountId, to: AccountId, amount: Balance) -> Result<(), CrossChainError> {
+            tests::move_tokens(token, self.own_account(), to, amount)
+        }
+
+        #[cfg(test)]
+        fn psp22_balance(&self, token: AccountId) -> Balance {
+            tests::balance(token, self.own_account())
+        }
+
+        /// Helper: smallest threshold that is a strict majority of `relayers`
+        fn majority(relayers: usize) -> u32 {
+            (relayers / 2 + 1) as u32
+        }
+
+        /// Helper: change the relayer threshold and emit an event
+        fn update_threshold(&mut self, new_threshold: u32) {
+            let old_threshold = self.relayer_threshold;
+            self.relayer_threshold = new_threshold;
+            self.env().emit_event(RelayerThresholdChanged { old_threshold, new_threshold });
+        }
+
+        /// Helper: the owner may settle transfers only while no relayers are configured
+        fn ensure_owner_settlement(&self) -> Result<(), CrossChainError> {
+            self.ensure_owner()?;
+            if !self.relayers.is_empty() {
+                return Err(CrossChainError::Unauthorized);
+            }
+            Ok(())
+        }
+
+        /// Mark a sent transfer as timed out once its confirmation window has passed.
+        /// Anyone can call this.
+        #[ink(message)]
+        pub fn expire_transfer(&mut self, transfer_id: u64) -> Result<(), CrossChainError> {
+            let transfer = self.transfers.get(transfer_id)
+                .ok_or(CrossChainError::TransferFailed)?;
+
+            if transfer.status != TransferStatus::Sent {
+                return Err(CrossChainError::InvalidTransition);
+            }
+            if !self.is_expired(&transfer) {
+                return Err(CrossChainError::TransferNotExpired);
+            }
+
+            self.time_out(transfer_id)
+        }
+
+        /// Refund a failed or timed-out transfer to its donor.
+        ///
+        /// Callable by the donor or the owner. A sent transfer whose confirmation
+        /// window has passed is timed out first. A batched donation that is
+        /// still pending once the window has passed since it was made is taken
+        /// out of its builder's batch and refunded from escrow.
+        ///
+        /// PSP22 escrow is still in custody and goes straight back. Assets held
+        /// through their precompile left with the XCM, so the contract must
+        /// first be topped up: once the owner reclaims the trapped assets on
+        /// the destination, or anyone transfers at least the donated amount
+        /// of the asset to the contract, the refund pays from that balance.
+        /// It never pays from other donors' escrow or credited inbound
+        /// donations, and fails with `EscrowSpent` until the top-up arrives.
+        #[ink(message)]
+        pub fn refund(&mut self, transfer_id: u64) -> Result<(), CrossChainError> {
+            let transfer = self.transfers.get(transfer_id)
+                .ok_or(CrossChainError::TransferFailed)?;
+
+            let caller = self.caller_account();
+            if caller != transfer.from && caller != self.owner {
+                return Err(CrossChainError::Unauthorized);
+            }
+
+            let status = if transfer.status == TransferStatus::Sent && self.is_expired(&transfer) {
+                self.time_out(transfer_id)?;
+                TransferStatus::TimedOut
+            } else {
+                transfer.status
+            };
+            if status == TransferStatus::Pending && !self.is_expired(&transfer) {
+                return Err(CrossChainError::TransferNotExpired);
+            }
+            if !status.can_transition_to(TransferStatus::Refunded) {
+                return Err(CrossChainError::InvalidTransition);
+            }
+
+            // A spent precompile donation waits for a top-up, leaving the
+            // transfer refundable once it arrives. A pending one never left.
+            let spent = Self::is_assets_precompile(&transfer.token) && status != TransferStatus::Pending;
+            if spent && transfer.amount > self.unreserved_balance(transfer.token) {
+                return Err(CrossChainError::EscrowSpent);
+            }
+
+            let transfer = self.transition(transfer_id, TransferStatus::Refunded)?;
+            if status == TransferStatus::Pending {
+                self.dequeue_payout(transfer.builder_id, transfer_id, transfer.amount);
+            }
+
+            let amount = if spent {
+                transfer.amount
+            } else {
+                let amount = self.release_escrow(transfer_id);
+                self.release_custody(transfer.token, amount);
+                amount
+            };
+            self.send_funds(transfer.token, transfer.from, amount)?;
+
+            self.env().emit_event(TransferRefunded {
+                transfer_id,
+                donor: transfer.from,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        /// Set how many blocks a sent transfer may wait for confirmation
+        #[ink(message)]
+        pub fn set_transfer_timeout(&mut self, blocks: BlockNumber) -> Result<(), CrossChainError> {
+            self.ensure_owner()?;
+            if blocks == 0 {
+                return Err(CrossChainError::InvalidTimeout);
+            }
+            self.transfer_timeout = blocks;
+            Ok(())
+        }
+
+        /// Get the confirmation window in blocks
+        #[ink(message)]
+        pub fn get_transfer_timeout(&self) -> BlockNumber {
+            self.transfer_timeout
+        }
+
+        /// Move a transfer to `next`, rejecting illegal transitions
+        fn transition(&mut self, transfer_id: u64, next: TransferStatus) -> Result<XcmTransfer, CrossChainError> {
+            let mut transfer = self.transfers.get(transfer_id)
+                .ok_or(CrossChainError::TransferFailed)?;
+
+            if !transfer.status.can_transition_to(next) {
+                return Err(CrossChainError::InvalidTransition);
+            }
+
+            transfer.status = next;
+            self.transfers.insert(transfer_id, &transfer);
+            Ok(transfer)
+        }
+
+        /// Helper: time out a sent transfer
+        fn time_out(&mut self, transfer_id: u64) -> Result<(), CrossChainError> {
+            let transfer = self.transition(transfer_id, TransferStatus::TimedOut)?;
+            self.env().emit_event(XcmTransferTimedOut {
+                transfer_id,
+                sent_at: transfer.sent_at,
+            });
+            Ok(())
+        }
+
+        /// Helper: take a refunded donation out of its builder's pending batch
+        fn dequeue_payout(&mut self, builder_id: u32, transfer_id: u64, amount: Balance) {
+            let Some(mut pending) = self.pending_payouts.get(builder_id) else {
+                return;
+            };
+            pending.transfer_ids.retain(|&id| id != transfer_id);
+            pending.amount = pending.amount.saturating_sub(amount);
+
+            if pending.transfer_ids.is_empty() {
+                self.pending_payouts.remove(builder_id);
+            } else {
+                self.pending_payouts.insert(builder_id, &pending);
+            }
+        }
+
+        /// Helper: whether a transfer's confirmation window has passed
+        fn is_expired(&self, transfer: &XcmTransfer) -> bool {
+            self.env().block_number() >= transfer.sent_at.saturating_add(self.transfer_timeout)
+        }
+
+        /// Add a new parachain builder
+        ///
+        /// `parachain_id` must be a registered, enabled parachain, and
+        /// `substrate_address` must match its account format: an SS58 address,
+        /// or a `0x`-prefixed H160 for EVM parachains.
+        #[ink(message)]
+        pub fn add_builder(
+            &mut self,
+            name: String,
+            project: String,
+            substrate_address: String,
+            parachain_id: u32,
+        ) -> Result<u32, CrossChainError> {
+            self.ensure_owner()?;
+            self.ensure_not_paused()?;
+
+            self.ensure_parachain_enabled(parachain_id)?;
+            let beneficiary = ss58::parse_beneficiary(&substrate_address, self.account_format(parachain_id))?;
+
+            self.builder_count += 1;
+            let builder_id = self.builder_count;
+
+            let builder = ParachainBuilder {
+                name,
+                project,
+                substrate_address,
+                beneficiary,
+                parachain_id,
+                total_received: 0,
+                active: true,
+                controller: None,
+            };
+
+            self.builders.insert(builder_id, &builder);
+            Ok(builder_id)
+        }
+
+        /// Get builder details
+        #[ink(message)]
+        pub fn get_builder(&self, builder_id: u32) -> Option<ParachainBuilder> {
+            self.builders.get(builder_id)
+        }
+
+        /// Get all builders
+        #[ink(message)]
+        pub fn get_all_builders(&self) -> Vec<(u32, ParachainBuilder)> {
+            let mut builders = Vec::new();
+            for id in 1..=self.builder_count {
+                if let Some(builder) = self.builders.get(id) {
+                    builders.push((id, builder));
+                }
+            }
+            builders
+        }
+
+        /// Get transfer details
+        #[ink(message)]
+        pub fn get_transfer(&self, transfer_id: u64) -> Option<XcmTransfer> {
+            self.transfers.get(transfer_id)
+        }
+
+        /// Get the hash of the XCM message sent for a transfer
+        #[ink(message)]
+        pub fn get_message_hash(&self, transfer_id: u64) -> Option<Hash> {
+            self.transfers.get(transfer_id).and_then(|transfer| transfer.message_hash)
+        }
+
+        /// Get the transfer created for a donor's idempotency key
+        #[ink(message)]
+        pub fn get_transfer_by_key(&self, donor: AccountId, idempotency_key: Hash) -> Option<u64> {
+            self.idempotency_keys.get((donor, idempotency_key))
+        }
+
+        /// Get the funds held in escrow for a transfer
+        ///
+        /// Donations held through an assets precompile leave with their XCM,
+        /// so they are only reported here while queued for a batch.
+        #[ink(message)]
+        pub fn get_escrowed(&self, transfer_id: u64) -> Balance {
+            self.escrow.get(transfer_id).unwrap_or(0)
+        }
+
+        /// Get the funds held in escrow across all transfers
+        #[ink(message)]
+        pub fn get_total_escrowed(&self) -> Balance {
+            self.total_escrowed
+        }
+
+        /// Recover tokens sent to the contract by mistake (only owner, while paused)
+        ///
+        /// Only the balance above the escrow still in custody and the credited
+        /// inbound donations in `token` can be recovered.
+        #[ink(message)]
+        pub fn recover_tokens(
+            &mut self,
+            token: AccountId,
+            to: AccountId,
+            amount: Balance,
+        ) -> Result<(), CrossChainError> {
+            self.ensure_owner()?;
+            self.ensure_paused()?;
+
+            if amount > self.unreserved_balance(token) {
+                return Err(CrossChainError::FundsReserved);
+            }
+
+            self.send_funds(token, to, amount)?;
+
+            self.env().emit_event(TokensRecovered { token, to, amount });
+            Ok(())
+        }
+
+        /// Recover native balance sent to the contract by mistake (only owner, while paused)
+        #[ink(message)]
+        pub fn recover_native(&mut self, to: H160, amount: U256) -> Result<(), CrossChainError> {
+            self.ensure_owner()?;
+            self.ensure_paused()?;
+
+            self.env().transfer(to, amount)
+                .map_err(|_| CrossChainError::TransferFailed)?;
+
+            self.env().emit_event(NativeRecovered { to, amount });
+            Ok(())
+        }
+
+        /// Register a parachain or replace its configuration
+        #[ink(message)]
+        pub fn register_parachain(&mut self, para_id: u32, config: ParachainConfig) -> Result<(), CrossChainError> {
+            self.ensure_owner()?;
+            if !(3..=5).contains(&config.xcm_version) {
+                return Err(CrossChainError::UnsupportedXcmVersion);
+            }
+
+            if !self.parachain_ids.contains(&para_id) {
+                self.parachain_ids.push(para_id);
+            }
+
+            self.env().emit_event(ParachainRegistered {
+                para_id,
+                name: config.name.clone(),
+                enabled: config.enabled,
+            });
+            self.parachains.insert(para_id, &config);
+            Ok(())
+        }
+
+        /// Enable or disable a parachain.
+        ///
+        /// Disabling blocks new builders and donations on it; existing builders are kept.
+        #[ink(message)]
+        pub fn set_parachain_enabled(&mut self, para_id: u32, enabled: bool) -> Result<(), CrossChainError> {
+            self.ensure_owner()?;
+
+            let mut config = self.parachains.get(para_id)
+                .ok_or(CrossChainError::InvalidParachain)?;
+            config.enabled = enabled;
+            self.parachains.insert(para_id, &config);
+
+            self.env().emit_event(ParachainStatusChanged { para_id, enabled });
+            Ok(())
+        }
+
+        /// Get a parachain's configuration
+        #[ink(message)]
+        pub fn get_parachain(&self, para_id: u32) -> Option<ParachainConfig> {
+            self.parachains.get(para_id)
+        }
+
+        /// Get all registered parachains
+        #[ink(message)]
+        pub fn get_all_parachains(&self) -> Vec<(u32, ParachainConfig)> {
+            self.parachain_ids
+                .iter()
+                .filter_map(|&id| self.parachains.get(id).map(|config| (id, config)))
+                .collect()
+        }
+
+        /// Add or replace the configuration of a supported asset
+        ///
+        /// An assets precompile `token` must be the precompile of `asset_id`.
+        #[ink(message)]
+        pub fn add_supported_asset(&mut self, asset_id: u32, config: AssetConfig) -> Result<(), CrossChainError> {
+            self.ensure_owner()?;
+            Self::ensure_valid_limits(config.min_donation, config.max_donation)?;
+            if assets_precompile::asset_id(config.token.as_ref()).is_some_and(|id| id != asset_id) {
+                return Err(CrossChainError::InvalidAddress);
+            }
+
+            self.env().emit_event(AssetConfigured {
+                asset_id,
+                token: config.token,
+                enabled: config.enabled,
+            });
+            self.assets.insert(asset_id, &config);
+            Ok(())
+        }
+
+        /// Update the donation limits of an asset
+        /// (mirrors `updateDonationLimits` in `SecureXCMDonation.sol`)
+        #[ink(message)]
+        pub fn update_donation_limits(
+            &mut self,
+            asset_id: u32,
+            min_donation: Balance,
+            max_donation: Balance,
+        ) -> Result<(), CrossChainError> {
+            self.ensure_owner()?;
+            Self::ensure_valid_limits(min_donation, max_donation)?;
+
+            let mut config = self.assets.get(asset_id)
+                .ok_or(CrossChainError::AssetNotSupported)?;
+            config.min_donation = min_donation;
+            config.max_donation = max_donation;
+            self.assets.insert(asset_id, &config);
+
+            self.env().emit_event(DonationLimitsUpdated {
+                asset_id,
+                min_donation,
+                max_donation,
+            });
+            Ok(())
+        }
+
+        /// Enable or disable donations of an asset without dropping its config
+        #[ink(message)]
+        pub fn set_asset_enabled(&mut self, asset_id: u32, enabled: bool) -> Result<(), CrossChainError> {
+            self.ensure_owner()?;
+
+            let mut config = self.assets.get(asset_id)
+                .ok_or(CrossChainError::AssetNotSupported)?;
+            config.enabled = enabled;
+            self.assets.insert(asset_id, &config);
+
+            self.env().emit_event(AssetConfigured {
+                asset_id,
+                token: config.token,
+                enabled,
+            });
+            Ok(())
+        }
+
+        /// Get the pallet-assets precompile account of an asset, usable as an `AssetConfig::token`
+        #[ink(message)]
+        pub fn get_assets_precompile(&self, asset_id: u32) -> AccountId {
+            AccountId::from(assets_precompile::account(asset_id))
+        }
+
+        /// Get the configuration of an asset
+        #[ink(message)]
+        pub fn get_asset_config(&self, asset_id: u32) -> Option<AssetConfig> {
+            self.assets.get(asset_id)
+        }
+
+        /// Check if asset is supported
+        #[ink(message)]
+        pub fn is_asset_supported(&self, asset_id: u32) -> bool {
+            self.assets.get(asset_id).is_some_and(|asset| asset.enabled)
+        }
+
+        /// Pause the contract
+        #[ink(message)]
+        pub fn pause(&mut self) -> Result<(), CrossChainError> {
+            self.ensure_owner()?;
+            self.paused = true;
+            Ok(())
+        }
+
+        /// Unpause the contract
+        #[ink(message)]
+        pub fn unpause(&mut self) -> Result<(), CrossChainError> {
+            self.ensure_owner()?;
+            self.paused = false;
+            Ok(())
+        }
+
+        /// Helper: ensure caller is the owner or the builder's controller
+        fn ensure_owner_or_controller(&self, builder: &ParachainBuilder) -> Result<(), CrossChainError> {
+            let caller = self.caller_account();
+            if caller != self.owner && builder.controller != Some(caller) {
+                return Err(CrossChainError::Unauthorized);
+            }
+            Ok(())
+        }
+
+        /// Helper: ensure a parachain is registered and enabled
+        fn ensure_parachain_enabled(&self, para_id: u32) -> Result<(), CrossChainError> {
+            let config = self.parachains.get(para_id)
+                .ok_or(CrossChainError::InvalidParachain)?;
+            if !config.enabled {
+                return Err(CrossChainError::ParachainDisabled);
+            }
+            Ok(())
+        }
+
+        /// Helper: account format of a registered parachain
+        fn account_format(&self, para_id: u32) -> Option<AccountFormat> {
+            self.parachains.get(para_id).map(|config| config.account_format)
+        }
+
+        /// Helper: validate donation limits like `SecureXCMDonation.sol`
+        fn ensure_valid_limits(min_donation: Balance, max_donation: Balance) -> Result<(), CrossChainError> {
+            if min_donation == 0 || max_donation <= min_donation {
+                return Err(CrossChainError::InvalidLimits);
+            }
+            Ok(())
+        }
+
+        /// Helper: ensure caller is owner
+        fn ensure_owner(&self) -> Result<(), CrossChainError> {
+            if self.caller_account() != self.owner {
+                return Err(CrossChainError::Unauthorized);
+            }
+            Ok(())
+        }
+
+        /// Helper: ensure contract is not paused
+        fn ensure_not_paused(&self) -> Result<(), CrossChainError> {
+            if self.paused {
+                return Err(CrossChainError::Unauthorized);
+            }
+            Ok(())
+        }
+
+        /// Helper: ensure contract is paused
+        fn ensure_paused(&self) -> Result<(), CrossChainError> {
+            if !self.paused {
+                return Err(CrossChainError::NotPaused);
+            }
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use hex_literal::hex;
+        use ink::env::test;
+        use ink::primitives::H160;
+        use scale::Encode;
+        use std::cell::RefCell;
+        use std::collections::HashMap;
+        use xcm::v4::{Fungibility, Instruction, Junction, Xcm};
+
+        /// Account the contract runs at
+        const CONTRACT: [u8; 20] = [0xC0; 20];
+        /// PSP22 USDC wrapper used by `create_contract`
+        const USDC: [u8; 32] = [0x01; 32];
+        /// XCM reserve the contracts are deployed with
+        const RESERVE: [u8; 32] = [0x7E; 32];
+
+        thread_local! {
+            /// Token balances by `(token, holder)`. The contract's AssetHub
+            /// balance of an asset is the one its precompile reports.
+            static LEDGER: RefCell<HashMap<(AccountId, AccountId), Balance>> = RefCell::new(HashMap::new());
+        }
+
+        pub fn balance(token: AccountId, holder: AccountId) -> Balance {
+            LEDGER.with(|ledger| ledger.borrow().get(&(token, holder)).copied().unwrap_or(0))
+        }
+
+        fn set_balance(token: AccountId, holder: AccountId, amount: Balance) {
+            LEDGER.with(|ledger| ledger.borrow_mut().insert((token, holder), amount));
+        }
+
+        fn mint(token: AccountId, holder: AccountId, amount: Balance) {
+            set_balance(token, holder, balance(token, holder) + amount);
+        }
+
+        pub fn move_tokens(token: AccountId, from: AccountId, to: AccountId, amount: Balance) -> Result<(), CrossChainError> {
+            let available = balance(token, from);
+            if available < amount {
+                return Err(CrossChainError::TokenTransferFailed);
+            }
+            set_balance(token, from, available - amount);
+            mint(token, to, amount);
+            Ok(())
+        }
+
+        /// Account behind an H160 the mock precompile was called with
+        fn account_at(address: &[u8]) -> Option<AccountId> {
+            let accounts = default_accounts();
+            [accounts.alice, accounts.bob, accounts.charlie, accounts.django, accounts.eve, accounts.frank, contract_account()]
+                .into_iter()
+                .find(|account| evm_interop::to_address(account.as_ref()).as_bytes() == address)
+        }
+
+        /// Mock assets precompile: ERC20 calls on the ledger, answered in raw
+        /// ABI like pallet-revive's own, with empty revert data on failure
+        pub fn call_assets_precompile(
+            caller: AccountId,
+            precompile: AccountId,
+            selector: [u8; 4],
+            args: &[u8],
+        ) -> Result<Vec<u8>, Vec<u8>> {
+            let word = |index: usize| args.get(index * 32..(index + 1) * 32).ok_or_else(Vec::new);
+            let account = |index: usize| word(index).and_then(|word| account_at(&word[12..]).ok_or_else(Vec::new));
+            let amount = |index: usize| word(index).and_then(|word| assets_precompile::decode_uint(word).ok_or_else(Vec::new));
+
+            let (from, to, value) = match selector {
+                assets_precompile::BALANCE_OF => {
+                    return Ok(assets_precompile::uint_word(balance(precompile, account(0)?)).to_vec());
+                }
+                assets_precompile::TRANSFER => (caller, account(0)?, amount(1)?),
+                assets_precompile::TRANSFER_FROM => (account(0)?, account(1)?, amount(2)?),
+                _ => return Err(Vec::new()),
+            };
+            move_tokens(precompile, from, to, value).map_err(|_| Vec::new())?;
+            Ok(assets_precompile::uint_word(1).to_vec())
+        }
+
+        /// Local XCM executor: `WithdrawAsset` debits the origin's AssetHub
+        /// balance, and everything after it leaves the chain
+        pub fn execute_xcm(origin: AccountId, message: &VersionedXcm<()>) -> Result<(), CrossChainError> {
+            let program = Xcm::<()>::try_from(message.clone())
+                .map_err(|_| CrossChainError::XcmExecutionFailed)?;
+
+            for instruction in program.0 {
+                let Instruction::WithdrawAsset(assets) = instruction else {
+                    continue;
+                };
+                for asset in assets.into_inner() {
+                    let asset_id = asset.id.0.interior().iter().find_map(|junction| match junction {
+                        Junction::GeneralIndex(index) => u32::try_from(*index).ok(),
+                        _ => None,
+                    });
+                    let (Some(asset_id), Fungibility::Fungible(amount)) = (asset_id, asset.fun) else {
+                        return Err(CrossChainError::XcmExecutionFailed);
+                    };
+
+                    let holding = AccountId::from(assets_precompile::account(asset_id));
+                    let available = balance(holding, origin);
+                    if available < amount {
+                        return Err(CrossChainError::XcmExecutionFailed);
+                    }
+                    set_balance(holding, origin, available - amount);
+                }
+            }
+            Ok(())
+        }
+
+        /// The default test accounts, as the accounts their H160s map to
+        struct Accounts {
+            alice: AccountId,
+            bob: AccountId,
+            charlie: AccountId,
+            django: AccountId,
+            eve: AccountId,
+            frank: AccountId,
+        }
+
+        fn default_accounts() -> Accounts {
+            let accounts = test::default_accounts();
+            let account = |address: H160| AccountId::from(evm_interop::to_account(address));
+            Accounts {
+                alice: account(accounts.alice),
+                bob: account(accounts.bob),
+                charlie: account(accounts.charlie),
+                django: account(accounts.django),
+                eve: account(accounts.eve),
+                frank: account(accounts.frank),
+            }
+        }
+
+        fn set_caller(account: AccountId) {
+            test::set_caller(evm_interop::to_address(account.as_ref()));
+        }
+
+        fn contract_account() -> AccountId {
+            AccountId::from(evm_interop::to_account(H160::from(CONTRACT)))
+        }
+
+        /// Deploy with `usdc_token` and give every default account 1,000 USDC and USDP
+        fn deploy(usdc_token: AccountId) -> CrossChainDonation {
+            test::set_callee(H160::from(CONTRACT));
+
+            let accounts = default_accounts();
+            set_caller(accounts.alice);
+            for account in [accounts.alice, accounts.bob, accounts.charlie, accounts.django, accounts.eve, accounts.frank] {
+                mint(usdc_token, account, 1_000_000_000);
+                mint(usdp_config().token, account, 1_000 * 10u128.pow(18));
+            }
+
+            // AssetHub at 1000, USDC at 1337
+            CrossChainDonation::new(1000, 1337, usdc_token, AccountId::from(RESERVE))
+        }
+
+        /// USDC held through a PSP22 wrapper; XCM transfers are funded from
+        /// the contract's own AssetHub balance
+        fn create_contract() -> CrossChainDonation {
+            let contract = deploy(AccountId::from(USDC));
+            mint(AccountId::from(assets_precompile::account(1337)), contract_account(), 1_000_000_000);
+            mint(AccountId::from(assets_precompile::account(42069)), contract_account(), 1_000 * 10u128.pow(18));
+            contract
+        }
+
+        #[ink::test]
+        fn constructor_works() {
+            let contract = create_contract();
+            assert_eq!(contract.builder_count, 3);
+            assert_eq!(contract.asset_hub_id, 1000);
+            assert_eq!(contract.usdc_asset_id, 1337);
+            assert!(contract.is_asset_supported(1337));
+            assert!(!contract.paused);
+        }
+
+        #[ink::test]
+        fn donate_creates_transfer() {
+            let mut contract = create_contract();
+
+            let result = contract.donate(1, 10_000_000, None); // 10 USDC
+            assert!(result.is_ok());
+
+            let transfer_id = result.unwrap();
+            let transfer = contract.get_transfer(transfer_id).unwrap();
+
+            assert_eq!(transfer.amount, 10_000_000);
+            assert_eq!(transfer.parachain_id, 2004); // Moonbeam
+            assert_eq!(transfer.status, TransferStatus::Sent);
+        }
+
+        const ALICE: [u8; 32] = hex!("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d");
+
+        #[ink::test]
+        fn ss58_decoding_works() {
+            // //Alice with the generic Substrate prefix
+            assert_eq!(
+                ss58::decode("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"),
+                Ok((42, ALICE))
+            );
+            // //Alice with the Polkadot prefix
+            assert_eq!(ss58::decode("15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5"), Ok((0, ALICE)));
+            // //Alice with the two-byte Moonbeam prefix (1284)
+            assert_eq!(
+                ss58::decode("VdvKmYJfD4VXA9fzz1SbmCo2eYHSzUFbaDCZSuaNKJAe8YNg6"),
+                Ok((1284, ALICE))
+            );
+
+            // Bad checksum (last character changed)
+            assert_eq!(
+                ss58::decode("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQZ"),
+                Err(AddressError::Invalid)
+            );
+            assert_eq!(ss58::decode("not-an-address"), Err(AddressError::Invalid));
+        }
+
+        #[ink::test]
+        fn parse_beneficiary_enforces_parachain_format() {
+            // Astar (prefix 5) accepts its own prefix...
+            assert_eq!(
+                ss58::parse_beneficiary("ajYMsCKsEAhEvHpeA4XqsfiA9v1CdzZPrCfS6pEfeGHW9j8", ss58::known_format(2006)),
+                Ok(Beneficiary::AccountId32(ALICE))
+            );
+            // ...the generic prefix only when configured for dev networks
+            assert_eq!(
+                ss58::parse_beneficiary("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY", ss58::known_format(2006)),
+                Err(AddressError::PrefixMismatch)
+            );
+            assert_eq!(
+                ss58::parse_beneficiary(
+                    "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+                    ss58::known_format(2006).map(AccountFormat::with_generic_prefix)
+                ),
+                Ok(Beneficiary::AccountId32(ALICE))
+            );
+            // ...but not a Polkadot address
+            assert_eq!(
+                ss58::parse_beneficiary("15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5", ss58::known_format(2006)),
+                Err(AddressError::PrefixMismatch)
+            );
+
+            // Moonbeam only accepts 20-byte accounts
+            assert_eq!(
+                ss58::parse_beneficiary("0xf24FF3a9CF04c71Dbc94D0b566f7A27B94566cac", ss58::known_format(2004)),
+                Ok(Beneficiary::AccountKey20(hex!("f24ff3a9cf04c71dbc94d0b566f7a27b94566cac")))
+            );
+            assert_eq!(
+                ss58::parse_beneficiary("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY", ss58::known_format(2004)),
+                Err(AddressError::PrefixMismatch)
+            );
+            assert_eq!(
+                ss58::parse_beneficiary("0xf24FF3a9CF04c71Dbc94D0b566f7A27B9456", ss58::known_format(2004)),
+                Err(AddressError::Invalid)
+            );
+        }
+
+        #[ink::test]
+        fn add_builder_rejects_invalid_addresses() {
+            let mut contract = create_contract();
+
+            // Typo breaks the checksum
+            assert_eq!(
+                contract.add_builder(
+                    "Typo".into(),
+                    "Broken address".into(),
+                    "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQZ".into(),
+                    2006,
+                ),
+                Err(CrossChainError::InvalidAddress)
+            );
+
+            // Substrate address on an EVM parachain
+            assert_eq!(
+                contract.add_builder(
+                    "Wrong chain".into(),
+                    "Substrate key on Moonbeam".into(),
+                    "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".into(),
+                    2004,
+                ),
+                Err(CrossChainError::AddressPrefixMismatch)
+            );
+
+            assert_eq!(contract.builder_count, 3);
+        }
+
+        #[ink::test]
+        fn xcm_message_to_sibling_parachain_encodes_exactly() {
+            let mut contract = create_contract();
+
+            let transfer_id = contract.donate(2, 10_000_000, None).unwrap(); // Bob on Astar (2006)
+            let transfer = contract.get_transfer(transfer_id).unwrap();
+            let message = contract.build_transfer_message(&transfer).unwrap();
+
+            assert_eq!(
+                message.encode(),
+                hex!(
+                    "04" // VersionedXcm::V4
+                    "08" // 2 instructions
+                    // WithdrawAsset((1, [Parachain(1000), PalletInstance(50), GeneralIndex(1337)]), 10 USDC)
+                    "00" "04" "01" "03" "00a10f" "0432" "05e514" "00025a6202"
+                    // InitiateReserveWithdraw { All(1), reserve: (1, [Parachain(1000)]) }
+                    "10" "010204" "01" "01" "00a10f"
+                    "08"
+                    // BuyExecution((0, [PalletInstance(50), GeneralIndex(1337)]), Unlimited)
+                    "13" "00" "02" "0432" "05e514" "00025a6202" "00"
+                    // DepositReserveAsset { All(1), dest: (1, [Parachain(2006)]) }
+                    "0e" "010204" "01" "01" "00591f"
+                    "08"
+                    // BuyExecution((1, [Parachain(1000), PalletInstance(50), GeneralIndex(1337)]), Unlimited)
+                    "13" "01" "03" "00a10f" "0432" "05e514" "00025a6202" "00"
+                    // DepositAsset { All(1), beneficiary: (0, [AccountId32 { network: None, id }]) }
+                    "0d" "010204" "00" "01" "01" "00"
+                    "8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48"
+                )
+                .to_vec()
+            );
+        }
+
+        #[ink::test]
+        fn xcm_message_to_evm_parachain_uses_account_key20() {
+            let mut contract = create_contract();
+
+            let transfer_id = contract.donate(1, 10_000_000, None).unwrap(); // Alith on Moonbeam (2004)
+            let transfer = contract.get_transfer(transfer_id).unwrap();
+            let message = contract.build_transfer_message(&transfer).unwrap();
+
+            assert_eq!(
+                message.encode(),
+                hex!(
+                    "04" "08"
+                    "00" "04" "01" "03" "00a10f" "0432" "05e514" "00025a6202"
+                    "10" "010204" "01" "01" "00a10f"
+                    "08"
+                    "13" "00" "02" "0432" "05e514" "00025a6202" "00"
+                    "0e" "010204" "01" "01" "00511f"
+                    "08"
+                    "13" "01" "03" "00a10f" "0432" "05e514" "00025a6202" "00"
+                    // DepositAsset { All(1), beneficiary: (0, [AccountKey20 { network: None, key }]) }
+                    "0d" "010204" "00" "01" "03" "00"
+                    "f24ff3a9cf04c71dbc94d0b566f7a27b94566cac"
+                )
+                .to_vec()
+            );
+        }
+
+        #[ink::test]
+        fn xcm_message_to_asset_hub_deposits_on_reserve() {
+            let mut contract = create_contract();
+
+            let builder_id = contract.add_builder(
+                "Eve - AssetHub".into(),
+                "Stablecoin tooling".into(),
+                "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".into(),
+                1000,
+            ).unwrap();
+
+            let transfer_id = contract.donate(builder_id, 10_000_000, None).unwrap();
+            let transfer = contract.get_transfer(transfer_id).unwrap();
+            let message = contract.build_transfer_message(&transfer).unwrap();
+
+            assert_eq!(
+                message.encode(),
+                hex!(
+                    "04" "08"
+                    "00" "04" "01" "03" "00a10f" "0432" "05e514" "00025a6202"
+                    "10" "010204" "01" "01" "00a10f"
+                    "08"
+                    "13" "00" "02" "0432" "05e514" "00025a6202" "00"
+                    "0d" "010204" "00" "01" "01" "00"
+                    "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"
+                )
+                .to_vec()
+            );
+        }
+
+        #[ink::test]
+        fn parachain_limits_apply_to_destination_execution() {
+            let mut contract = create_contract();
+
+            let mut astar = contract.get_parachain(2006).unwrap();
+            astar.weight_limit = Some(XcmWeight { ref_time: 1_000_000_000, proof_size: 65_536 });
+            astar.fee_budget = Some(100_000); // 0.1 USDC
+            contract.register_parachain(2006, astar).unwrap();
+
+            let transfer_id = contract.donate(2, 10_000_000, None).unwrap(); // Bob on Astar (2006)
+            let transfer = contract.get_transfer(transfer_id).unwrap();
+            let message = contract.build_transfer_message(&transfer).unwrap();
+
+            assert_eq!(
+                message.encode(),
+                hex!(
+                    "04" "08"
+                    "00" "04" "01" "03" "00a10f" "0432" "05e514" "00025a6202"
+                    "10" "010204" "01" "01" "00a10f"
+                    "08"
+                    "13" "00" "02" "0432" "05e514" "00025a6202" "00"
+                    "0e" "010204" "01" "01" "00591f"
+                    "08"
+                    // BuyExecution { fees: 0.1 USDC, weight_limit: Limited(1_000_000_000, 65_536) }
+                    "13" "01" "03" "00a10f" "0432" "05e514" "00821a0600" "01" "02286bee" "02000400"
+                    "0d" "010204" "00" "01" "01" "00"
+                    "8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48"
+                )
+                .to_vec()
+            );
+        }
+
+        #[ink::test]
+        fn parachain_registry_works() {
+            let mut contract = create_contract();
+
+            assert_eq!(contract.get_all_parachains().len(), 5);
+            assert_eq!(contract.get_parachain(2004).unwrap().account_format, AccountFormat::AccountKey20);
+
+            let bifrost = ParachainConfig {
+                name: "Bifrost".into(),
+                account_format: AccountFormat::AccountId32 { prefix: 6, accepts_generic: true },
+                xcm_version: 4,
+                weight_limit: None,
+                fee_budget: None,
+                unit_weight: DEFAULT_UNIT_WEIGHT,
+                fee_per_second: DEFAULT_FEE_PER_SECOND,
+                enabled: true,
+            };
+
+            let mut unsupported = bifrost.clone();
+            unsupported.xcm_version = 2;
+            assert_eq!(
+                contract.register_parachain(2030, unsupported),
+                Err(CrossChainError::UnsupportedXcmVersion)
+            );
+
+            // Unregistered parachains can't take builders
+            assert_eq!(
+                contract.add_builder(
+                    "Eve - Bifrost".into(),
+                    "Liquid staking".into(),
+                    "5HGjWAeFDfFCWPsjFQdVV2Msvz2XtMktvgocEZcCj68kUMaw".into(),
+                    2030,
+                ),
+                Err(CrossChainError::InvalidParachain)
+            );
+
+            contract.register_parachain(2030, bifrost.clone()).unwrap();
+            assert_eq!(contract.get_parachain(2030), Some(bifrost));
+            assert_eq!(contract.get_all_parachains().len(), 6);
+
+            assert!(contract
+                .add_builder(
+                    "Eve - Bifrost".into(),
+                    "Liquid staking".into(),
+                    "5HGjWAeFDfFCWPsjFQdVV2Msvz2XtMktvgocEZcCj68kUMaw".into(),
+                    2030,
+                )
+                .is_ok());
+        }
+
+        #[ink::test]
+        fn disabled_parachain_blocks_donations_and_builders() {
+            let mut contract = create_contract();
+
+            contract.set_parachain_enabled(2006, false).unwrap();
+
+            // Bob on Astar is kept, but can't receive new donations
+            assert!(contract.get_builder(2).is_some());
+            assert_eq!(contract.donate(2, 10_000_000, None), Err(CrossChainError::ParachainDisabled));
+            assert_eq!(
+                contract.add_builder(
+                    "Ferdie - Astar".into(),
+                    "dApp staking".into(),
+                    "5CiPPseXPECbkjWCa6MnjNokrgYjMqmKndv2rSnekmSK2DjL".into(),
+                    2006,
+                ),
+                Err(CrossChainError::ParachainDisabled)
+            );
+
+            // Other parachains are unaffected
+            assert!(contract.donate(1, 10_000_000, None).is_ok());
+
+            contract.set_parachain_enabled(2006, true).unwrap();
+            assert!(contract.donate(2, 10_000_000, None).is_ok());
+
+            assert_eq!(contract.set_parachain_enabled(9999, false), Err(CrossChainError::InvalidParachain));
+        }
+
+        #[ink::test]
+        fn idempotency_key_prevents_double_donation() {
+            let mut contract = create_contract();
+            let accounts = default_accounts();
+            let key = Hash::from([0xAB; 32]);
+
+            let transfer_id = contract.donate(1, 10_000_000, Some(key)).unwrap();
+            let events = test::recorded_events().count();
+
+            // A retry returns the original transfer without side effects
+            assert_eq!(contract.donate(1, 10_000_000, Some(key)), Ok(transfer_id));
+            assert_eq!(contract.transfer_nonce, 1);
+            assert_eq!(contract.get_total_escrowed(), 10_000_000);
+            assert_eq!(test::recorded_events().count(), events);
+            assert_eq!(contract.get_transfer_by_key(accounts.alice, key), Some(transfer_id));
+
+            // Reusing the key for a different donation is rejected
+            assert_eq!(
+                contract.donate(2, 10_000_000, Some(key)),
+                Err(CrossChainError::IdempotencyKeyReused)
+            );
+
+            // Keys are scoped per donor
+            set_caller(accounts.bob);
+            let other = contract.donate(1, 10_000_000, Some(key)).unwrap();
+            assert_ne!(other, transfer_id);
+
+            // No key, no deduplication
+            let first = contract.donate(1, 10_000_000, None).unwrap();
+            let second = contract.donate(1, 10_000_000, None).unwrap();
+            assert_ne!(first, second);
+        }
+
+        #[ink::test]
+        fn message_hash_is_recorded() {
+            let mut contract = create_contract();
+
+            let transfer_id = contract.donate(2, 10_000_000, None).unwrap();
+            let transfer = contract.get_transfer(transfer_id).unwrap();
+            let message = contract.build_transfer_message(&transfer).unwrap();
+
+            let mut expected = [0u8; 32];
+            ink::env::hash_bytes::<Blake2x256>(&message.encode(), &mut expected);
+            assert_eq!(contract.get_message_hash(transfer_id), Some(Hash::from(expected)));
+            assert_eq!(contract.get_message_hash(999), None);
+        }
+
+        #[ink::test]
+        fn estimate_donation_to_sibling_parachain() {
+            let contract = create_contract();
+
+            // AssetHub runs WithdrawAsset, ClearOrigin, BuyExecution, DepositReserveAsset;
+            // Astar runs ReserveAssetDeposited, ClearOrigin, BuyExecution, DepositAsset
+            let estimate = contract.estimate_donation(2, 1337, 10_000_000).unwrap();
+            assert_eq!(
+                estimate,
+                DonationEstimate {
+                    weight: XcmWeight { ref_time: 8_000_000_000, proof_size: 8 * 65_536 },
+                    fee_asset_id: 1337,
+                    fee: 80_000, // 0.01 USDC per instruction
+                    net_amount: 9_920_000,
+                }
+            );
+
+            // Estimating has no side effects
+            assert_eq!(contract.transfer_nonce, 0);
+        }
+
+        #[ink::test]
+        fn estimate_donation_uses_parachain_fee_parameters() {
+            let mut contract = create_contract();
+
+            let builder_id = contract.add_builder(
+                "Eve - AssetHub".into(),
+                "Stablecoin tooling".into(),
+                "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".into(),
+                1000,
+            ).unwrap();
+
+            // Only one hop when the builder lives on the reserve
+            let estimate = contract.estimate_donation(builder_id, 1337, 10_000_000).unwrap();
+            assert_eq!(estimate.fee, 40_000);
+            assert_eq!(estimate.weight.ref_time, 4_000_000_000);
+
+            // Astar gets twice as expensive
+            let mut astar = contract.get_parachain(2006).unwrap();
+            astar.fee_per_second = 2 * DEFAULT_FEE_PER_SECOND;
+            contract.register_parachain(2006, astar.clone()).unwrap();
+            assert_eq!(contract.estimate_donation(2, 1337, 10_000_000).unwrap().fee, 40_000 + 80_000);
+
+            // A budget too small to cover Astar's execution is flagged
+            astar.fee_budget = Some(50_000);
+            contract.register_parachain(2006, astar).unwrap();
+            assert_eq!(
+                contract.estimate_donation(2, 1337, 10_000_000),
+                Err(CrossChainError::FeeBudgetExceeded)
+            );
+
+            // Same validation as donating
+            assert_eq!(contract.estimate_donation(1, 9999, 10_000_000), Err(CrossChainError::AssetNotSupported));
+            assert_eq!(contract.estimate_donation(1, 1337, 500_000), Err(CrossChainError::InvalidAmount));
+        }
+
+        #[ink::test]
+        fn donate_invalid_amount() {
+            let mut contract = create_contract();
+
+            let result = contract.donate(1, 500_000, None); // 0.5 USDC (below minimum)
+            assert_eq!(result, Err(CrossChainError::InvalidAmount));
+        }
+
+        #[ink::test]
+        fn donate_invalid_builder() {
+            let mut contract = create_contract();
+
+            let result = contract.donate(999, 10_000_000, None);
+            assert_eq!(result, Err(CrossChainError::InvalidBuilder));
+        }
+
+        #[ink::test]
+        fn confirm_transfer_works() {
+            let mut contract = create_contract();
+
+            let transfer_id = contract.donate(1, 10_000_000, None).unwrap();
+            assert!(contract.confirm_transfer(transfer_id).is_ok());
+
+            let transfer = contract.get_transfer(transfer_id).unwrap();
+            assert_eq!(transfer.status, TransferStatus::Confirmed);
+
+            // Builder total only counts confirmed transfers
+            assert_eq!(contract.get_builder(1).unwrap().total_received, 10_000_000);
+
+            // Confirming twice is rejected
+            assert_eq!(contract.confirm_transfer(transfer_id), Err(CrossChainError::InvalidTransition));
+        }
+
+        #[ink::test]
+        fn escrow_is_held_until_settled() {
+            let mut contract = create_contract();
+
+            let confirmed = contract.donate(1, 10_000_000, None).unwrap();
+            let refunded = contract.donate(2, 5_000_000, None).unwrap();
+            assert_eq!(contract.get_escrowed(confirmed), 10_000_000);
+            assert_eq!(contract.get_escrowed(refunded), 5_000_000);
+            assert_eq!(contract.get_total_escrowed(), 15_000_000);
+
+            contract.confirm_transfer(confirmed).unwrap();
+            assert_eq!(contract.get_escrowed(confirmed), 0);
+            assert_eq!(contract.get_total_escrowed(), 5_000_000);
+
+            // Failed transfers keep their escrow until refunded
+            contract.report_failure(refunded, "Barrier".into()).unwrap();
+            assert_eq!(contract.get_escrowed(refunded), 5_000_000);
+
+            contract.refund(refunded).unwrap();
+            assert_eq!(contract.get_escrowed(refunded), 0);
+            assert_eq!(contract.get_total_escrowed(), 0);
+        }
+
+        #[ink::test]
+        fn recovery_leaves_escrow_untouched() {
+            let mut contract = create_contract();
+            let accounts = default_accounts();
+            let usdc = AccountId::from(USDC);
+
+            let transfer_id = contract.donate(2, 5_000_000, None).unwrap();
+            mint(usdc, contract_account(), 1_000_000); // Sent by mistake
+
+            // Only while paused, and only by the owner
+            assert_eq!(contract.recover_tokens(usdc, accounts.eve, 1_000_000), Err(CrossChainError::NotPaused));
+            contract.pause().unwrap();
+            set_caller(accounts.bob);
+            assert_eq!(contract.recover_tokens(usdc, accounts.eve, 1_000_000), Err(CrossChainError::Unauthorized));
+            assert_eq!(contract.recover_native(test::default_accounts().eve, U256::from(1_000)), Err(CrossChainError::Unauthorized));
+            set_caller(accounts.alice);
+
+            // The stray 1 USDC can go, the donor's 5 USDC cannot
+            assert_eq!(contract.recover_tokens(usdc, accounts.eve, 1_000_001), Err(CrossChainError::FundsReserved));
+            assert!(contract.recover_tokens(usdc, accounts.eve, 1_000_000).is_ok());
+            assert_eq!(contract.get_escrowed(transfer_id), 5_000_000);
+            assert_eq!(balance(usdc, accounts.eve), 1_001_000_000);
+
+            // Refunds still work while paused and release the reservation
+            contract.report_failure(transfer_id, "Barrier".into()).unwrap();
+            contract.refund(transfer_id).unwrap();
+            assert_eq!(contract.token_escrow.get(usdc), Some(0));
+
+            let eve = test::default_accounts().eve;
+            test::set_account_balance(test::callee(), U256::from(5_000_000));
+            let before = test::get_account_balance::<Environment>(eve).unwrap();
+            assert!(contract.recover_native(eve, U256::from(1_000_000)).is_ok());
+            assert_eq!(test::get_account_balance::<Environment>(eve).unwrap(), before + U256::from(1_000_000));
+        }
+
+        #[ink::test]
+        fn psp22_escrow_reimburses_the_xcm_on_confirm() {
+            let mut contract = create_contract();
+            let accounts = default_accounts();
+            let usdc = AccountId::from(USDC);
+            let asset_hub_usdc = AccountId::from(assets_precompile::account(1337));
+
+            set_caller(accounts.bob);
+            let transfer_id = contract.donate(1, 10_000_000, None).unwrap();
+
+            // The donor's tokens sit in escrow while the XCM spends the
+            // contract's AssetHub balance
+            assert_eq!(balance(usdc, accounts.bob), 990_000_000);
+            assert_eq!(balance(usdc, contract_account()), 10_000_000);
+            assert_eq!(balance(asset_hub_usdc, contract_account()), 990_000_000);
+
+            // On delivery the escrow goes to the reserve that funds that balance
+            set_caller(accounts.alice);
+            contract.confirm_transfer(transfer_id).unwrap();
+            assert_eq!(balance(usdc, contract_account()), 0);
+            assert_eq!(balance(usdc, AccountId::from(RESERVE)), 10_000_000);
+            assert_eq!(balance(usdc, accounts.alice), 1_000_000_000);
+            assert_eq!(contract.token_escrow.get(usdc), Some(0));
+
+            // ...so there is nothing left to sweep
+            contract.pause().unwrap();
+            assert_eq!(contract.recover_tokens(usdc, accounts.eve, 1), Err(CrossChainError::FundsReserved));
+        }
+
+        #[ink::test]
+        fn xcm_reserve_is_paid_for_delivered_transfers() {
+            let mut contract = create_contract();
+            let accounts = default_accounts();
+            let usdc = AccountId::from(USDC);
+            assert_eq!(contract.get_xcm_reserve(), AccountId::from(RESERVE));
+
+            let before = contract.donate(1, 10_000_000, None).unwrap();
+            let after = contract.donate(2, 5_000_000, None).unwrap();
+
+            // Moving the reserve only redirects escrow not yet paid out
+            contract.confirm_transfer(before).unwrap();
+            contract.set_xcm_reserve(accounts.django).unwrap();
+            contract.confirm_transfer(after).unwrap();
+            assert_eq!(balance(usdc, AccountId::from(RESERVE)), 10_000_000);
+            assert_eq!(balance(usdc, accounts.django), 1_005_000_000);
+            assert_eq!(balance(usdc, contract_account()), 0);
+        }
+
+        #[ink::test]
+        fn psp22_refund_returns_the_escrow_once() {
+            let mut contract = create_contract();
+            let accounts = default_accounts();
+            let usdc = AccountId::from(USDC);
+
+            set_caller(accounts.bob);
+            let transfer_id = contract.donate(1, 10_000_000, None).unwrap();
+            let other = contract.donate(2, 5_000_000, None).unwrap();
+
+            set_caller(accounts.alice);
+            contract.report_failure(transfer_id, "Barrier".into()).unwrap();
+
+            set_caller(accounts.bob);
+            contract.refund(transfer_id).unwrap();
+            assert_eq!(balance(usdc, accounts.bob), 995_000_000);
+            assert_eq!(contract.refund(transfer_id), Err(CrossChainError::InvalidTransition));
+
+            // The other donation's escrow is untouched
+            assert_eq!(balance(usdc, contract_account()), 5_000_000);
+            assert_eq!(contract.get_escrowed(other), 5_000_000);
+        }
+
+        #[ink::test]
+        fn escrow_stays_in_the_donated_token() {
+            let mut contract = create_contract();
+            let accounts = default_accounts();
+            let usdc = AccountId::from(USDC);
+            let new_usdc = AccountId::from([0x03; 32]);
+
+            set_caller(accounts.bob);
+            let confirmed = contract.donate(1, 10_000_000, None).unwrap();
+            let refunded = contract.donate(2, 5_000_000, None).unwrap();
+            assert_eq!(contract.get_transfer(confirmed).unwrap().token, usdc);
+
+            // Moving USDC to a new wrapper doesn't move existing escrow
+            set_caller(accounts.alice);
+            let config = AssetConfig { token: new_usdc, ..contract.get_asset_config(1337).unwrap() };
+            contract.add_supported_asset(1337, config).unwrap();
+
+            contract.confirm_transfer(confirmed).unwrap();
+            assert_eq!(balance(usdc, AccountId::from(RESERVE)), 10_000_000);
+            assert_eq!(balance(new_usdc, AccountId::from(RESERVE)), 0);
+
+            contract.report_failure(refunded, "Barrier".into()).unwrap();
+            set_caller(accounts.bob);
+            contract.refund(refunded).unwrap();
+            assert_eq!(balance(usdc, accounts.bob), 990_000_000);
+            assert_eq!(balance(usdc, contract_account()), 0);
+            assert_eq!(contract.token_escrow.get(usdc), Some(0));
+        }
+
+        #[ink::test]
+        fn precompile_escrow_funds_the_xcm() {
+            let mut contract = deploy(AccountId::from(assets_precompile::account(1337)));
+            let accounts = default_accounts();
+            let usdc = AccountId::from(assets_precompile::account(1337));
+
+            contract
+                .set_payout_preferences(2, 2006, "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty".into(), 1337, 25_000_000)
+                .unwrap();
+
+            // The contract holds no AssetHub balance of its own, so the
+            // donation itself pays for the transfer
+            set_caller(accounts.bob);
+            let failed = contract.donate(1, 10_000_000, None).unwrap();
+            assert_eq!(balance(usdc, accounts.bob), 990_000_000);
+            assert_eq!(balance(usdc, contract_account()), 0);
+            assert_eq!(contract.token_escrow.get(usdc), Some(0));
+            assert_eq!(contract.get_escrowed(failed), 0);
+
+            // A queued donation is still in custody
+            let queued = contract.donate(2, 5_000_000, None).unwrap();
+            assert_eq!(balance(usdc, contract_account()), 5_000_000);
+            assert_eq!(contract.token_escrow.get(usdc), Some(5_000_000));
+            assert_eq!(contract.get_total_escrowed(), 5_000_000);
+
+            set_caller(accounts.alice);
+            contract.report_failure(failed, "Barrier".into()).unwrap();
+
+            // The failed donation already left, and the queued one isn't its to take
+            set_caller(accounts.bob);
+            assert_eq!(contract.refund(failed), Err(CrossChainError::EscrowSpent));
+            assert_eq!(balance(usdc, accounts.bob), 985_000_000);
+            assert_eq!(contract.get_escrowed(queued), 5_000_000);
+        }
+
+        #[ink::test]
+        fn precompile_refund_pays_from_reclaimed_funds() {
+            let mut contract = deploy(AccountId::from(assets_precompile::account(1337)));
+            let accounts = default_accounts();
+            let usdc = AccountId::from(assets_precompile::account(1337));
+
+            set_caller(accounts.bob);
+            let transfer_id = contract.donate(1, 10_000_000, None).unwrap();
+            assert_eq!(contract.get_escrowed(transfer_id), 0);
+            assert_eq!(contract.get_total_escrowed(), 0);
+
+            set_caller(accounts.alice);
+            contract.report_failure(transfer_id, "Barrier".into()).unwrap();
+
+            // Nothing to refund from until the contract is topped up
+            set_caller(accounts.bob);
+            assert_eq!(contract.refund(transfer_id), Err(CrossChainError::EscrowSpent));
+            mint(usdc, contract_account(), 6_000_000);
+            assert_eq!(contract.refund(transfer_id), Err(CrossChainError::EscrowSpent));
+            assert_eq!(contract.get_transfer(transfer_id).unwrap().status, TransferStatus::Failed);
+
+            // The owner claims the rest of the trapped assets back into the contract
+            mint(usdc, contract_account(), 4_000_000);
+
+            contract.refund(transfer_id).unwrap();
+            assert_eq!(balance(usdc, accounts.bob), 1_000_000_000);
+            assert_eq!(balance(usdc, contract_account()), 0);
+            assert_eq!(contract.get_total_escrowed(), 0);
+        }
+
+        #[ink::test]
+        fn donate_does_not_count_until_confirmed() {
+            let mut contract = create_contract();
+
+            contract.donate(1, 10_000_000, None).unwrap();
+            assert_eq!(contract.get_builder(1).unwrap().total_received, 0);
+        }
+
+        #[ink::test]
+        fn report_failure_and_refund_works() {
+            let mut contract = create_contract();
+            let accounts = default_accounts();
+
+            let transfer_id = contract.donate(1, 10_000_000, None).unwrap();
+            assert!(contract.report_failure(transfer_id, "FailedToTransactAsset".into()).is_ok());
+
+            let transfer = contract.get_transfer(transfer_id).unwrap();
+            assert_eq!(transfer.status, TransferStatus::Failed);
+            assert_eq!(transfer.failure_reason, Some("FailedToTransactAsset".into()));
+
+            // A failed transfer can't be confirmed or failed again
+            assert_eq!(contract.confirm_transfer(transfer_id), Err(CrossChainError::InvalidTransition));
+            assert_eq!(
+                contract.report_failure(transfer_id, "again".into()),
+                Err(CrossChainError::InvalidTransition)
+            );
+
+            // Only the donor or owner can refund
+            set_caller(accounts.bob);
+            assert_eq!(contract.refund(transfer_id), Err(CrossChainError::Unauthorized));
+
+            set_caller(accounts.alice);
+            assert!(contract.refund(transfer_id).is_ok());
+            assert_eq!(contract.get_transfer(transfer_id).unwrap().status, TransferStatus::Refunded);
+            assert_eq!(contract.refund(transfer_id), Err(CrossChainError::InvalidTransition));
+            assert_eq!(contract.get_builder(1).unwrap().total_received, 0);
+        }
+
+        #[ink::test]
+        fn refund_requires_failure_or_timeout() {
+            let mut contract = create_contract();
+
+            let transfer_id = contract.donate(1, 10_000_000, None).unwrap();
+            assert_eq!(contract.refund(transfer_id), Err(CrossChainError::InvalidTransition));
+
+            contract.confirm_transfer(transfer_id).unwrap();
+            assert_eq!(contract.refund(transfer_id), Err(CrossChainError::InvalidTransition));
+        }
+
+        #[ink::test]
+        fn transfer_times_out_after_window() {
+            let mut contract = create_contract();
+            assert!(contract.set_transfer_timeout(5).is_ok());
+            assert_eq!(contract.set_transfer_timeout(0), Err(CrossChainError::InvalidTimeout));
+
+            let transfer_id = contract.donate(1, 10_000_000, None).unwrap();
+            assert_eq!(contract.expire_transfer(transfer_id), Err(CrossChainError::TransferNotExpired));
+
+            for _ in 0..5 {
+                test::advance_block::<Environment>();
+            }
+
+            // Anyone can expire the transfer
+            set_caller(default_accounts().bob);
+            assert!(contract.expire_transfer(transfer_id).is_ok());
+            assert_eq!(contract.get_transfer(transfer_id).unwrap().status, TransferStatus::TimedOut);
+            assert_eq!(contract.expire_transfer(transfer_id), Err(CrossChainError::InvalidTransition));
+
+            // A late confirmation still wins before a refund
+            set_caller(default_accounts().alice);
+            assert!(contract.confirm_transfer(transfer_id).is_ok());
+            assert_eq!(contract.get_builder(1).unwrap().total_received, 10_000_000);
+        }
+
+        #[ink::test]
+        fn refund_times_out_expired_transfer() {
+            let mut contract = create_contract();
+            contract.set_transfer_timeout(2).unwrap();
+
+            let transfer_id = contract.donate(1, 10_000_000, None).unwrap();
+            test::advance_block::<Environment>();
+            test::advance_block::<Environment>();
+
+            assert!(contract.refund(transfer_id).is_ok());
+            assert_eq!(contract.get_transfer(transfer_id).unwrap().status, TransferStatus::Refunded);
+            assert_eq!(contract.confirm_transfer(transfer_id), Err(CrossChainError::InvalidTransition));
+        }
+
+        #[ink::test]
+        fn add_builder_works() {
+            let mut contract = create_contract();
+
+            let result = contract.add_builder(
+                "Dave - HydraDX".into(),
+                "Liquidity Protocol".into(),
+                "5DAAnrj7VHTznn2AWBemMuyBwZWs6FNFjdyVXUeYum3PTXFy".into(),
+                2034,
+            );
 
-            self.builder_count += 1;
-            let builder_id = self.builder_count;
+            assert!(result.is_ok());
+            assert_eq!(contract.builder_count, 4);
 
-            let builder = ParachainBuilder {
-                name,
-                project,
-                substrate_address,
-                parachain_id,
-                total_received: 0,
-                active: true,
-            };
+            let builder = contract.get_builder(4).unwrap();
+            assert_eq!(builder.name, "Dave - HydraDX");
+            assert_eq!(builder.parachain_id, 2034);
+        }
 
-            self.builders.insert(builder_id, &builder);
-            Ok(builder_id)
+        fn usdp_config() -> AssetConfig {
+            AssetConfig {
+                token: AccountId::from([0x02; 32]),
+                decimals: 18,
+                min_donation: 10u128.pow(18),
+                max_donation: 50_000 * 10u128.pow(18),
+                fee_asset_id: 1337,
+                enabled: true,
+            }
         }
 
-        /// Get builder details
-        #[ink(message)]
-        pub fn get_builder(&self, builder_id: u32) -> Option<ParachainBuilder> {
-            self.builders.get(builder_id)
+        #[ink::test]
+        fn assets_precompile_backend_is_configured_by_asset_id() {
+            let precompile = AccountId::from(hex!(
+                "0000053900000000000000000000000001200000eeeeeeeeeeeeeeeeeeeeeeee"
+            ));
+            let mut contract = deploy(precompile);
+
+            // Native AssetHub USDC, no wrapper contract
+            assert_eq!(contract.get_assets_precompile(1337), precompile);
+            assert!(CrossChainDonation::is_assets_precompile(&precompile));
+            assert!(!CrossChainDonation::is_assets_precompile(&AccountId::from([0x01; 32])));
+            assert!(contract.donate(1, 5_000_000, None).is_ok());
+            assert_eq!(balance(precompile, default_accounts().alice), 995_000_000);
+
+            // ...whose donation left with the XCM rather than sitting in escrow
+            assert_eq!(contract.get_total_escrowed(), 0);
+
+            // A precompile only stands in for its own asset
+            let mut config = usdp_config();
+            config.token = contract.get_assets_precompile(1984);
+            assert_eq!(contract.add_supported_asset(42069, config.clone()), Err(CrossChainError::InvalidAddress));
+            assert!(contract.add_supported_asset(1984, config).is_ok());
         }
 
-        /// Get all builders
-        #[ink(message)]
-        pub fn get_all_builders(&self) -> Vec<(u32, ParachainBuilder)> {
-            let mut builders = Vec::new();
-            for id in 1..=self.builder_count {
-                if let Some(builder) = self.builders.get(id) {
-                    builders.push((id, builder));
-                }
-            }
-            builders
+        #[ink::test]
+        fn precompile_custody_goes_through_its_erc20_interface() {
+            let contract = deploy(AccountId::from(assets_precompile::account(1337)));
+            let accounts = default_accounts();
+            let usdc = AccountId::from(assets_precompile::account(1337));
+            mint(usdc, contract_account(), 7_000_000);
+
+            // Balances come back as a raw `uint256` word
+            assert_eq!(contract.token_balance(usdc), 7_000_000);
+
+            // Transfers succeed on an ABI `true`
+            assert!(contract.pull_funds(usdc, accounts.bob, 3_000_000).is_ok());
+            assert!(contract.send_funds(usdc, accounts.eve, 10_000_000).is_ok());
+            assert_eq!(contract.token_balance(usdc), 0);
+            assert_eq!(balance(usdc, accounts.bob), 997_000_000);
+            assert_eq!(balance(usdc, accounts.eve), 1_010_000_000);
+
+            // ...and fail on a revert
+            assert_eq!(contract.send_funds(usdc, accounts.eve, 1), Err(CrossChainError::TokenTransferFailed));
         }
 
-        /// Get transfer details
-        #[ink(message)]
-        pub fn get_transfer(&self, transfer_id: u64) -> Option<XcmTransfer> {
-            self.transfers.get(transfer_id)
+        #[ink::test]
+        fn assets_precompile_calls_are_abi_encoded() {
+            let precompile = assets_precompile::account(1337);
+            assert_eq!(assets_precompile::asset_id(&precompile), Some(1337));
+            assert_eq!(evm_interop::to_address(&precompile).0, hex!("0000053900000000000000000000000001200000"));
+
+            let donor = [0x01; 32];
+            let args = assets_precompile::transfer_from_args(&donor, &precompile, 5_000_000);
+            assert_eq!(args.len(), 96);
+            assert_eq!(args[..12], [0u8; 12]);
+            assert_eq!(args[12..32], evm_interop::to_address(&donor).0);
+            assert_eq!(args[44..64], hex!("0000053900000000000000000000000001200000"));
+            assert_eq!(args[64..], assets_precompile::uint_word(5_000_000));
+
+            assert!(assets_precompile::is_success(&[]));
+            assert!(assets_precompile::is_success(&assets_precompile::uint_word(1)));
+            assert!(!assets_precompile::is_success(&assets_precompile::uint_word(0)));
+            assert_eq!(assets_precompile::decode_uint(&assets_precompile::uint_word(42)), Some(42));
         }
 
-        /// Add supported asset
-        #[ink(message)]
-        pub fn add_supported_asset(&mut self, asset_id: u32) -> Result<(), CrossChainError> {
-            self.ensure_owner()?;
-            self.supported_assets.insert(asset_id, &true);
-            Ok(())
+        #[ink::test]
+        fn add_supported_asset_works() {
+            let mut contract = create_contract();
+
+            assert!(contract.add_supported_asset(42069, usdp_config()).is_ok()); // USDP
+            assert!(contract.is_asset_supported(42069));
+            assert_eq!(contract.get_asset_config(42069), Some(usdp_config()));
+
+            let mut invalid = usdp_config();
+            invalid.max_donation = invalid.min_donation;
+            assert_eq!(contract.add_supported_asset(1, invalid), Err(CrossChainError::InvalidLimits));
         }
 
-        /// Check if asset is supported
-        #[ink(message)]
-        pub fn is_asset_supported(&self, asset_id: u32) -> bool {
-            self.supported_assets.get(asset_id).unwrap_or(false)
+        #[ink::test]
+        fn donate_asset_uses_asset_limits() {
+            let mut contract = create_contract();
+            contract.add_supported_asset(42069, usdp_config()).unwrap();
+
+            // 1 USDC is fine for USDC but far below the USDP minimum
+            assert_eq!(contract.donate_asset(1, 42069, 1_000_000, None), Err(CrossChainError::InvalidAmount));
+            assert_eq!(
+                contract.donate_asset(1, 42069, 50_001 * 10u128.pow(18), None),
+                Err(CrossChainError::InvalidAmount)
+            );
+
+            let transfer_id = contract.donate_asset(1, 42069, 5 * 10u128.pow(18), None).unwrap();
+            assert_eq!(contract.get_transfer(transfer_id).unwrap().asset_id, 42069);
+
+            assert_eq!(contract.donate_asset(1, 9999, 10_000_000, None), Err(CrossChainError::AssetNotSupported));
         }
 
-        /// Pause the contract
-        #[ink(message)]
-        pub fn pause(&mut self) -> Result<(), CrossChainError> {
-            self.ensure_owner()?;
-            self.paused = true;
-            Ok(())
+        #[ink::test]
+        fn update_donation_limits_works() {
+            let mut contract = create_contract();
+
+            assert_eq!(contract.update_donation_limits(1337, 0, 10), Err(CrossChainError::InvalidLimits));
+            assert_eq!(contract.update_donation_limits(1337, 10, 10), Err(CrossChainError::InvalidLimits));
+            assert_eq!(
+                contract.update_donation_limits(9999, 1, 10),
+                Err(CrossChainError::AssetNotSupported)
+            );
+
+            contract.update_donation_limits(1337, 5_000_000, 20_000_000).unwrap();
+            assert_eq!(contract.donate(1, 4_000_000, None), Err(CrossChainError::InvalidAmount));
+            assert_eq!(contract.donate(1, 25_000_000, None), Err(CrossChainError::InvalidAmount));
+            assert!(contract.donate(1, 5_000_000, None).is_ok());
         }
 
-        /// Unpause the contract
-        #[ink(message)]
-        pub fn unpause(&mut self) -> Result<(), CrossChainError> {
-            self.ensure_owner()?;
-            self.paused = false;
-            Ok(())
+        #[ink::test]
+        fn disabled_asset_rejects_donations() {
+            let mut contract = create_contract();
+
+            contract.set_asset_enabled(1337, false).unwrap();
+            assert!(!contract.is_asset_supported(1337));
+            assert_eq!(contract.donate(1, 10_000_000, None), Err(CrossChainError::AssetNotSupported));
+
+            contract.set_asset_enabled(1337, true).unwrap();
+            assert!(contract.donate(1, 10_000_000, None).is_ok());
         }
 
-        /// Helper: ensure caller is owner
-        fn ensure_owner(&self) -> Result<(), CrossChainError> {
-            if self.env().caller() != self.owner {
-                return Err(CrossChainError::Unauthorized);
-            }
-            Ok(())
+        #[ink::test]
+        fn pause_works() {
+            let mut contract = create_contract();
+
+            assert!(contract.pause().is_ok());
+            assert!(contract.paused);
+
+            let result = contract.donate(1, 10_000_000, None);
+            assert_eq!(result, Err(CrossChainError::Unauthorized));
+
+            assert!(contract.unpause().is_ok());
+            assert!(!contract.paused);
         }
 
-        /// Helper: ensure contract is not paused
-        fn ensure_not_paused(&self) -> Result<(), CrossChainError> {
-            if self.paused {
-                return Err(CrossChainError::Unauthorized);
+        fn setup_relayers(contract: &mut CrossChainDonation, threshold: u32) {
+            let accounts = default_accounts();
+            for relayer in [accounts.bob, accounts.charlie, accounts.django] {
+                contract.add_relayer(relayer).unwrap();
             }
-            Ok(())
+            contract.set_relayer_threshold(threshold).unwrap();
         }
-    }
 
-    #[cfg(test)]
-    mod tests {
-        use super::*;
-        use ink::env::test;
+        #[ink::test]
+        fn relayer_set_management_works() {
+            let mut contract = create_contract();
+            let accounts = default_accounts();
+
+            assert_eq!(contract.get_relayers(), (vec![], 0));
+            assert_eq!(contract.set_relayer_threshold(1), Err(CrossChainError::InvalidThreshold));
+
+            contract.add_relayer(accounts.bob).unwrap();
+            assert_eq!(contract.get_relayers(), (vec![accounts.bob], 1));
+            assert_eq!(contract.add_relayer(accounts.bob), Err(CrossChainError::RelayerAlreadyExists));
+
+            // A second relayer raises the threshold to a majority
+            contract.add_relayer(accounts.charlie).unwrap();
+            assert_eq!(contract.get_relayers(), (vec![accounts.bob, accounts.charlie], 2));
+            assert_eq!(contract.set_relayer_threshold(3), Err(CrossChainError::InvalidThreshold));
+
+            // Removing a relayer lowers an unreachable threshold
+            contract.remove_relayer(accounts.bob).unwrap();
+            assert_eq!(contract.get_relayers(), (vec![accounts.charlie], 1));
+            assert_eq!(contract.remove_relayer(accounts.bob), Err(CrossChainError::NotRelayer));
 
-        fn default_accounts() -> test::DefaultAccounts<Environment> {
-            test::default_accounts::<Environment>()
+            // Removing the last relayer hands settlement back to the owner
+            contract.remove_relayer(accounts.charlie).unwrap();
+            assert_eq!(contract.get_relayers(), (vec![], 0));
         }
 
-        fn create_contract() -> CrossChainDonation {
-            CrossChainDonation::new(1000, 1337) // AssetHub at 1000, USDC at 1337
+        #[ink::test]
+        fn threshold_must_be_a_majority() {
+            let mut contract = create_contract();
+            let accounts = default_accounts();
+            setup_relayers(&mut contract, 2);
+
+            // One of three could settle a transfer the other two dispute
+            assert_eq!(contract.set_relayer_threshold(1), Err(CrossChainError::InvalidThreshold));
+            contract.set_relayer_threshold(3).unwrap();
+            contract.set_relayer_threshold(2).unwrap();
+
+            // A fourth relayer makes 2 a tie, so the threshold rises to 3
+            contract.add_relayer(accounts.eve).unwrap();
+            assert_eq!(contract.get_relayers().1, 3);
+            assert_eq!(contract.set_relayer_threshold(2), Err(CrossChainError::InvalidThreshold));
         }
 
         #[ink::test]
-        fn constructor_works() {
-            let contract = create_contract();
-            assert_eq!(contract.builder_count, 3);
-            assert_eq!(contract.asset_hub_id, 1000);
-            assert_eq!(contract.usdc_asset_id, 1337);
-            assert!(contract.is_asset_supported(1337));
-            assert!(!contract.paused);
+        fn threshold_attestations_confirm_transfer() {
+            let mut contract = create_contract();
+            let accounts = default_accounts();
+            setup_relayers(&mut contract, 2);
+
+            let transfer_id = contract.donate(1, 10_000_000, None).unwrap();
+            let block_hash = Hash::from([0x42; 32]);
+
+            // Owner can no longer settle on their own
+            assert_eq!(contract.confirm_transfer(transfer_id), Err(CrossChainError::Unauthorized));
+
+            // Non-relayers can't attest
+            assert_eq!(
+                contract.attest_transfer(transfer_id, TransferOutcome::Delivered, block_hash),
+                Err(CrossChainError::NotRelayer)
+            );
+
+            set_caller(accounts.bob);
+            contract.attest_transfer(transfer_id, TransferOutcome::Delivered, block_hash).unwrap();
+            assert_eq!(
+                contract.attest_transfer(transfer_id, TransferOutcome::Delivered, block_hash),
+                Err(CrossChainError::AlreadyAttested)
+            );
+            assert_eq!(contract.get_transfer(transfer_id).unwrap().status, TransferStatus::Sent);
+
+            set_caller(accounts.charlie);
+            contract.attest_transfer(transfer_id, TransferOutcome::Delivered, block_hash).unwrap();
+
+            let transfer = contract.get_transfer(transfer_id).unwrap();
+            assert_eq!(transfer.status, TransferStatus::Confirmed);
+            assert_eq!(transfer.dest_block_hash, Some(block_hash));
+            assert_eq!(contract.get_builder(1).unwrap().total_received, 10_000_000);
+            assert!(contract.get_attestations(transfer_id).is_empty());
+
+            // Settled transfers take no further attestations
+            set_caller(accounts.django);
+            assert_eq!(
+                contract.attest_transfer(transfer_id, TransferOutcome::Delivered, block_hash),
+                Err(CrossChainError::InvalidTransition)
+            );
         }
 
         #[ink::test]
-        fn donate_creates_transfer() {
+        fn conflicting_attestations_are_disputed() {
             let mut contract = create_contract();
+            let accounts = default_accounts();
+            setup_relayers(&mut contract, 2);
 
-            let result = contract.donate(1, 10_000_000); // 10 USDC
-            assert!(result.is_ok());
+            let transfer_id = contract.donate(1, 10_000_000, None).unwrap();
+            let emitted_before = test::recorded_events().count();
+
+            set_caller(accounts.bob);
+            contract.attest_transfer(transfer_id, TransferOutcome::Delivered, Hash::from([0x01; 32])).unwrap();
+
+            set_caller(accounts.charlie);
+            contract.attest_transfer(transfer_id, TransferOutcome::Failed, Hash::from([0x01; 32])).unwrap();
+
+            // TransferAttested x2 + AttestationDisputed, and nothing settled
+            assert_eq!(test::recorded_events().count(), emitted_before + 3);
+            assert_eq!(contract.get_transfer(transfer_id).unwrap().status, TransferStatus::Sent);
+            assert_eq!(contract.get_attestations(transfer_id).len(), 2);
+
+            // A second failure attestation reaches the threshold
+            set_caller(accounts.django);
+            contract.attest_transfer(transfer_id, TransferOutcome::Failed, Hash::from([0x01; 32])).unwrap();
 
-            let transfer_id = result.unwrap();
             let transfer = contract.get_transfer(transfer_id).unwrap();
+            assert_eq!(transfer.status, TransferStatus::Failed);
+            assert_eq!(contract.get_builder(1).unwrap().total_received, 0);
+        }
 
-            assert_eq!(transfer.amount, 10_000_000);
-            assert_eq!(transfer.parachain_id, 2004); // Moonbeam
+        #[ink::test]
+        fn removed_relayer_attestations_do_not_count() {
+            let mut contract = create_contract();
+            let accounts = default_accounts();
+            setup_relayers(&mut contract, 2);
+
+            let transfer_id = contract.donate(1, 10_000_000, None).unwrap();
+            let block_hash = Hash::from([0x07; 32]);
+
+            set_caller(accounts.bob);
+            contract.attest_transfer(transfer_id, TransferOutcome::Delivered, block_hash).unwrap();
+
+            set_caller(accounts.alice);
+            contract.remove_relayer(accounts.bob).unwrap();
+            assert!(contract.get_attestations(transfer_id).is_empty());
+
+            set_caller(accounts.charlie);
+            contract.attest_transfer(transfer_id, TransferOutcome::Delivered, block_hash).unwrap();
+            assert_eq!(contract.get_transfer(transfer_id).unwrap().status, TransferStatus::Sent);
+            assert_eq!(contract.get_attestations(transfer_id).len(), 1);
+
+            set_caller(accounts.django);
+            contract.attest_transfer(transfer_id, TransferOutcome::Delivered, block_hash).unwrap();
+            assert_eq!(contract.get_transfer(transfer_id).unwrap().status, TransferStatus::Confirmed);
+        }
+
+        #[ink::test]
+        fn payout_preferences_route_donations() {
+            let mut contract = create_contract();
+            let accounts = default_accounts();
+
+            // Defaults follow the builder's registration
+            let prefs = contract.get_payout_preferences(2).unwrap();
+            assert_eq!((prefs.parachain_id, prefs.asset_id, prefs.min_batch), (2006, 1337, 0));
+
+            contract.add_supported_asset(42069, usdp_config()).unwrap();
+            contract.set_builder_controller(2, Some(accounts.bob)).unwrap();
+
+            // Bob moves his payouts to AssetHub, in USDP
+            set_caller(accounts.bob);
+            assert_eq!(
+                contract.set_payout_preferences(2, 1000, "0xf24FF3a9CF04c71Dbc94D0b566f7A27B94566cac".into(), 42069, 0),
+                Err(CrossChainError::AddressPrefixMismatch)
+            );
+            assert_eq!(
+                contract.set_payout_preferences(2, 1000, "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty".into(), 9999, 0),
+                Err(CrossChainError::AssetNotSupported)
+            );
+            contract
+                .set_payout_preferences(2, 1000, "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty".into(), 42069, 0)
+                .unwrap();
+
+            // Only the owner or the controller may change preferences
+            set_caller(accounts.charlie);
+            assert_eq!(
+                contract.set_payout_preferences(2, 2006, "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty".into(), 1337, 0),
+                Err(CrossChainError::Unauthorized)
+            );
+
+            // `donate` now sends USDP to AssetHub
+            let transfer_id = contract.donate(2, 5 * 10u128.pow(18), None).unwrap();
+            let transfer = contract.get_transfer(transfer_id).unwrap();
+            assert_eq!(transfer.parachain_id, 1000);
+            assert_eq!(transfer.asset_id, 42069);
             assert_eq!(transfer.status, TransferStatus::Sent);
         }
 
         #[ink::test]
-        fn donate_invalid_amount() {
+        fn small_donations_are_batched() {
             let mut contract = create_contract();
+            let accounts = default_accounts();
 
-            let result = contract.donate(1, 500_000); // 0.5 USDC (below minimum)
-            assert_eq!(result, Err(CrossChainError::InvalidAmount));
+            contract
+                .set_payout_preferences(2, 2006, "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty".into(), 1337, 25_000_000)
+                .unwrap();
+
+            let first = contract.donate(2, 10_000_000, None).unwrap();
+            let second = contract.donate(2, 10_000_000, None).unwrap();
+            assert_eq!(contract.get_transfer(first).unwrap().status, TransferStatus::Pending);
+            assert_eq!(
+                contract.get_pending_payout(2),
+                PendingPayout { amount: 20_000_000, transfer_ids: vec![first, second] }
+            );
+
+            // Preferences are locked while a batch is pending
+            assert_eq!(
+                contract.set_payout_preferences(2, 2006, "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty".into(), 1337, 0),
+                Err(CrossChainError::PayoutPending)
+            );
+
+            // A keeper must wait for the minimum
+            set_caller(accounts.charlie);
+            assert_eq!(contract.flush_payout(2), Err(CrossChainError::BatchBelowMinimum));
+
+            set_caller(accounts.alice);
+            let third = contract.donate(2, 5_000_000, None).unwrap();
+
+            // ...then anyone can send one XCM for the whole batch
+            set_caller(accounts.charlie);
+            let hash = contract.flush_payout(2).unwrap();
+            assert_eq!(contract.get_pending_payout(2), PendingPayout::default());
+            assert_eq!(contract.flush_payout(2), Err(CrossChainError::NothingToFlush));
+
+            for transfer_id in [first, second, third] {
+                let transfer = contract.get_transfer(transfer_id).unwrap();
+                assert_eq!(transfer.status, TransferStatus::Sent);
+                assert_eq!(transfer.message_hash, Some(hash));
+            }
+
+            let batch = XcmTransfer { amount: 25_000_000, ..contract.get_transfer(first).unwrap() };
+            let message = contract.build_transfer_message(&batch).unwrap();
+            let mut expected = [0u8; 32];
+            ink::env::hash_bytes::<Blake2x256>(&message.encode(), &mut expected);
+            assert_eq!(hash, Hash::from(expected));
+
+            // Each donation still settles on its own
+            set_caller(accounts.alice);
+            contract.confirm_transfer(second).unwrap();
+            assert_eq!(contract.get_builder(2).unwrap().total_received, 10_000_000);
+
+            // Donations in other assets skip the batch
+            contract.add_supported_asset(42069, usdp_config()).unwrap();
+            let usdp = contract.donate_asset(2, 42069, 5 * 10u128.pow(18), None).unwrap();
+            assert_eq!(contract.get_transfer(usdp).unwrap().status, TransferStatus::Sent);
+            assert_eq!(contract.get_pending_payout(2), PendingPayout::default());
         }
 
         #[ink::test]
-        fn donate_invalid_builder() {
+        fn expired_batched_donation_is_refunded() {
             let mut contract = create_contract();
+            let accounts = default_accounts();
+            let usdc = AccountId::from(USDC);
+            contract.set_transfer_timeout(2).unwrap();
+            contract
+                .set_payout_preferences(2, 2006, "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty".into(), 1337, 25_000_000)
+                .unwrap();
 
-            let result = contract.donate(999, 10_000_000);
-            assert_eq!(result, Err(CrossChainError::InvalidBuilder));
+            let before = balance(usdc, accounts.alice);
+            let transfer_id = contract.donate(2, 10_000_000, None).unwrap();
+            assert_eq!(contract.refund(transfer_id), Err(CrossChainError::TransferNotExpired));
+
+            test::advance_block::<Environment>();
+            test::advance_block::<Environment>();
+
+            // Only the donor or the owner can pull it out of the batch
+            set_caller(accounts.charlie);
+            assert_eq!(contract.refund(transfer_id), Err(CrossChainError::Unauthorized));
+            set_caller(accounts.alice);
+
+            assert!(contract.refund(transfer_id).is_ok());
+            assert_eq!(contract.get_transfer(transfer_id).unwrap().status, TransferStatus::Refunded);
+            assert_eq!(contract.get_pending_payout(2), PendingPayout::default());
+            assert_eq!(contract.get_escrowed(transfer_id), 0);
+            assert_eq!(balance(usdc, accounts.alice), before);
+
+            // Nothing is left for the batch to send
+            assert_eq!(contract.flush_payout(2), Err(CrossChainError::NothingToFlush));
         }
 
         #[ink::test]
-        fn confirm_transfer_works() {
+        fn builder_can_flush_early() {
             let mut contract = create_contract();
+            let accounts = default_accounts();
 
-            let transfer_id = contract.donate(1, 10_000_000).unwrap();
-            assert!(contract.confirm_transfer(transfer_id).is_ok());
+            contract.set_builder_controller(2, Some(accounts.bob)).unwrap();
+            contract
+                .set_payout_preferences(2, 2006, "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty".into(), 1337, 25_000_000)
+                .unwrap();
+            assert_eq!(contract.flush_payout(2), Err(CrossChainError::NothingToFlush));
 
-            let transfer = contract.get_transfer(transfer_id).unwrap();
-            assert_eq!(transfer.status, TransferStatus::Confirmed);
+            let transfer_id = contract.donate(2, 10_000_000, None).unwrap();
+
+            set_caller(accounts.bob);
+            let hash = contract.flush_payout(2).unwrap();
+            assert_eq!(contract.get_message_hash(transfer_id), Some(hash));
+            assert_eq!(contract.get_transfer(transfer_id).unwrap().status, TransferStatus::Sent);
+
+            // With the batch flushed, preferences can change again
+            assert!(contract
+                .set_payout_preferences(2, 2006, "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty".into(), 1337, 0)
+                .is_ok());
+        }
+
+        fn hydration_supporter() -> InboundOrigin {
+            InboundOrigin {
+                para_id: 2034,
+                account: Beneficiary::AccountId32(ALICE),
+            }
         }
 
         #[ink::test]
-        fn add_builder_works() {
+        fn credit_inbound_works() {
             let mut contract = create_contract();
+            let accounts = default_accounts();
+            let reference = Hash::from([0x33; 32]);
 
-            let result = contract.add_builder(
-                "Dave - HydraDX".into(),
-                "Liquidity Protocol".into(),
-                "5DAAnrj7VHTznn2AWBemMuyBwZWs6FNFjdyVXUeYum3PTXFy".into(),
-                2034,
+            // Neither executor nor relayer yet
+            assert_eq!(
+                contract.credit_inbound(hydration_supporter(), 1337, 25_000_000, 2, reference),
+                Err(CrossChainError::Unauthorized)
             );
 
-            assert!(result.is_ok());
-            assert_eq!(contract.builder_count, 4);
+            contract.set_inbound_executor(Some(accounts.eve)).unwrap();
+            contract.add_relayer(accounts.bob).unwrap();
+            contract.add_relayer(accounts.charlie).unwrap();
+
+            // A relayer's report is still outstanding when the executor credits
+            set_caller(accounts.bob);
+            assert_eq!(contract.credit_inbound(hydration_supporter(), 1337, 25_000_000, 2, reference), Ok(None));
+
+            set_caller(accounts.eve);
+            let inbound_id = contract
+                .credit_inbound(hydration_supporter(), 1337, 25_000_000, 2, reference)
+                .unwrap()
+                .unwrap();
+            let donation = contract.get_inbound_donation(inbound_id).unwrap();
+            assert_eq!(donation.origin, hydration_supporter());
+            assert_eq!(donation.builder_id, 2);
+            assert_eq!(donation.asset_id, 1337);
+            assert_eq!(donation.credited_by, accounts.eve);
+            assert_eq!(contract.get_inbound_by_reference(reference), Some(inbound_id));
+            assert_eq!(contract.get_builder(2).unwrap().total_received, 25_000_000);
+            assert_eq!(contract.get_inbound_claimable(2, 1337), 25_000_000);
+            assert!(contract.get_inbound_attestations(reference).is_empty());
+
+            // The same reference is only credited once
+            assert_eq!(
+                contract.credit_inbound(hydration_supporter(), 1337, 25_000_000, 2, reference),
+                Err(CrossChainError::DuplicateReference)
+            );
+            assert_eq!(contract.get_builder(2).unwrap().total_received, 25_000_000);
 
-            let builder = contract.get_builder(4).unwrap();
-            assert_eq!(builder.name, "Dave - HydraDX");
-            assert_eq!(builder.parachain_id, 2034);
+            assert_eq!(
+                contract.credit_inbound(hydration_supporter(), 1337, 1, 999, Hash::from([0x34; 32])),
+                Err(CrossChainError::InvalidBuilder)
+            );
         }
 
         #[ink::test]
-        fn add_supported_asset_works() {
+        fn credit_inbound_records_the_received_asset() {
             let mut contract = create_contract();
+            let accounts = default_accounts();
+            contract.set_inbound_executor(Some(accounts.eve)).unwrap();
 
-            assert!(contract.add_supported_asset(42069).is_ok()); // USDP
-            assert!(contract.is_asset_supported(42069));
+            set_caller(accounts.eve);
+            assert_eq!(
+                contract.credit_inbound(hydration_supporter(), 42069, 10u128.pow(18), 2, Hash::from([0x35; 32])),
+                Err(CrossChainError::AssetNotSupported)
+            );
+
+            set_caller(accounts.alice);
+            contract.add_supported_asset(42069, usdp_config()).unwrap();
+
+            set_caller(accounts.eve);
+            let inbound_id = contract
+                .credit_inbound(hydration_supporter(), 42069, 10u128.pow(18), 2, Hash::from([0x35; 32]))
+                .unwrap()
+                .unwrap();
+            assert_eq!(contract.get_inbound_donation(inbound_id).unwrap().asset_id, 42069);
         }
 
         #[ink::test]
-        fn pause_works() {
+        fn relayers_credit_inbound_at_threshold() {
             let mut contract = create_contract();
+            let accounts = default_accounts();
+            setup_relayers(&mut contract, 2);
+            let reference = Hash::from([0x01; 32]);
+            let origin = InboundOrigin {
+                para_id: 2004,
+                account: Beneficiary::AccountKey20(hex!("f24ff3a9cf04c71dbc94d0b566f7a27b94566cac")),
+            };
 
-            assert!(contract.pause().is_ok());
-            assert!(contract.paused);
+            // A single relayer only attests
+            set_caller(accounts.bob);
+            assert_eq!(contract.credit_inbound(origin, 1337, 5_000_000, 3, reference), Ok(None));
+            assert_eq!(
+                contract.credit_inbound(origin, 1337, 5_000_000, 3, reference),
+                Err(CrossChainError::AlreadyAttested)
+            );
+            assert_eq!(contract.get_builder(3).unwrap().total_received, 0);
+
+            // A report of a different amount doesn't add up
+            set_caller(accounts.charlie);
+            assert_eq!(contract.credit_inbound(origin, 1337, 50_000_000, 3, reference), Ok(None));
+            assert_eq!(contract.get_inbound_attestations(reference).len(), 2);
+            assert_eq!(contract.get_inbound_by_reference(reference), None);
+
+            // A second matching report credits the donation
+            set_caller(accounts.django);
+            let inbound_id = contract.credit_inbound(origin, 1337, 5_000_000, 3, reference).unwrap().unwrap();
+            assert_eq!(contract.get_inbound_donation(inbound_id).unwrap().credited_by, accounts.django);
+            assert_eq!(contract.get_builder(3).unwrap().total_received, 5_000_000);
+            assert!(contract.get_inbound_attestations(reference).is_empty());
+
+            set_caller(accounts.eve);
+            assert_eq!(
+                contract.credit_inbound(origin, 1337, 5_000_000, 3, Hash::from([0x02; 32])),
+                Err(CrossChainError::Unauthorized)
+            );
+        }
 
-            let result = contract.donate(1, 10_000_000);
-            assert_eq!(result, Err(CrossChainError::Unauthorized));
+        #[ink::test]
+        fn inbound_credits_are_reserved_until_claimed() {
+            let mut contract = deploy(AccountId::from(assets_precompile::account(1337)));
+            let accounts = default_accounts();
+            let usdc = AccountId::from(assets_precompile::account(1337));
+            contract.set_inbound_executor(Some(accounts.eve)).unwrap();
+
+            // 25 USDC arrives at the contract's sovereign account for builder 2
+            mint(usdc, contract_account(), 25_000_000);
+            set_caller(accounts.eve);
+            contract.credit_inbound(hydration_supporter(), 1337, 25_000_000, 2, Hash::from([0x36; 32])).unwrap();
+
+            // A failed precompile donation can't be refunded out of it
+            set_caller(accounts.bob);
+            let failed = contract.donate(1, 10_000_000, None).unwrap();
+            assert_eq!(balance(usdc, contract_account()), 25_000_000);
+            set_caller(accounts.alice);
+            contract.report_failure(failed, "Barrier".into()).unwrap();
+            set_caller(accounts.bob);
+            assert_eq!(contract.refund(failed), Err(CrossChainError::EscrowSpent));
+
+            // ...nor swept by recovery
+            set_caller(accounts.alice);
+            contract.pause().unwrap();
+            assert_eq!(contract.recover_tokens(usdc, accounts.eve, 1), Err(CrossChainError::FundsReserved));
+            contract.unpause().unwrap();
+
+            // Anyone can send it on to the builder's payout destination
+            set_caller(accounts.charlie);
+            assert_eq!(contract.claim_inbound(1, 1337), Err(CrossChainError::NothingToClaim));
+            let hash = contract.claim_inbound(2, 1337).unwrap();
+            assert_eq!(balance(usdc, contract_account()), 0);
+            assert_eq!(contract.get_inbound_claimable(2, 1337), 0);
+            assert_eq!(contract.claim_inbound(2, 1337), Err(CrossChainError::NothingToClaim));
+
+            let route = contract.get_payout_preferences(2).unwrap();
+            let message = contract.build_message(route.parachain_id, 1337, 25_000_000, route.beneficiary).unwrap();
+            assert_eq!(hash, CrossChainDonation::message_hash(&message));
+        }
 
-            assert!(contract.unpause().is_ok());
-            assert!(!contract.paused);
+        #[ink::test]
+        fn outbound_transfers_leave_inbound_credits_alone() {
+            let mut contract = create_contract();
+            let accounts = default_accounts();
+            let asset_hub_usdc = AccountId::from(assets_precompile::account(1337));
+            contract.set_inbound_executor(Some(accounts.eve)).unwrap();
+
+            // Inbound USDC lands in the AssetHub balance that PSP22 donations spend
+            set_balance(asset_hub_usdc, contract_account(), 30_000_000);
+            set_caller(accounts.eve);
+            contract.credit_inbound(hydration_supporter(), 1337, 25_000_000, 2, Hash::from([0x37; 32])).unwrap();
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.donate(1, 10_000_000, None), Err(CrossChainError::FundsReserved));
+            assert!(contract.donate(1, 5_000_000, None).is_ok());
+            assert_eq!(balance(asset_hub_usdc, contract_account()), 25_000_000);
+        }
+
+        #[ink::test]
+        fn removed_relayer_inbound_reports_do_not_count() {
+            let mut contract = create_contract();
+            let accounts = default_accounts();
+            setup_relayers(&mut contract, 2);
+            let reference = Hash::from([0x05; 32]);
+
+            set_caller(accounts.bob);
+            contract.credit_inbound(hydration_supporter(), 1337, 5_000_000, 2, reference).unwrap();
+
+            set_caller(accounts.alice);
+            contract.remove_relayer(accounts.bob).unwrap();
+
+            set_caller(accounts.charlie);
+            assert_eq!(contract.credit_inbound(hydration_supporter(), 1337, 5_000_000, 2, reference), Ok(None));
+            assert_eq!(contract.get_builder(2).unwrap().total_received, 0);
         }
 
         #[ink::test]
@@ -491,11 +3830,21 @@ pub mod cross_chain {
             let mut contract = create_contract();
             let accounts = default_accounts();
 
-            test::set_caller::<Environment>(accounts.bob);
+            set_caller(accounts.bob);
 
             assert_eq!(contract.pause(), Err(CrossChainError::Unauthorized));
             assert_eq!(contract.confirm_transfer(1), Err(CrossChainError::Unauthorized));
-            assert_eq!(contract.add_supported_asset(123), Err(CrossChainError::Unauthorized));
+            assert_eq!(contract.report_failure(1, "no".into()), Err(CrossChainError::Unauthorized));
+            assert_eq!(contract.set_transfer_timeout(10), Err(CrossChainError::Unauthorized));
+            assert_eq!(contract.add_relayer(accounts.bob), Err(CrossChainError::Unauthorized));
+            assert_eq!(contract.set_relayer_threshold(1), Err(CrossChainError::Unauthorized));
+            assert_eq!(contract.add_supported_asset(123, usdp_config()), Err(CrossChainError::Unauthorized));
+            assert_eq!(contract.update_donation_limits(1337, 1, 10), Err(CrossChainError::Unauthorized));
+            assert_eq!(contract.set_asset_enabled(1337, false), Err(CrossChainError::Unauthorized));
+            assert_eq!(contract.set_parachain_enabled(2006, false), Err(CrossChainError::Unauthorized));
+            assert_eq!(contract.set_inbound_executor(Some(accounts.bob)), Err(CrossChainError::Unauthorized));
+            assert_eq!(contract.set_xcm_reserve(accounts.bob), Err(CrossChainError::Unauthorized));
+            assert_eq!(contract.set_builder_controller(1, Some(accounts.bob)), Err(CrossChainError::Unauthorized));
         }
     }
 }
\ No newline at end of file
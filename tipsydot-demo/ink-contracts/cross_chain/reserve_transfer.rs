@@ -0,0 +1,165 @@
+//! XCM program construction for reserve-backed USDC donations
+//!
+//! USDC is a `pallet-assets` asset whose reserve is AssetHub. Sending it to
+//! a builder on another parachain is a reserve withdrawal:
+//!
+//! 1. `WithdrawAsset` the donation from the contract's account locally
+//! 2. `InitiateReserveWithdraw` to AssetHub, which
+//!    - `BuyExecution` with the donated asset
+//!    - `DepositReserveAsset` to the builder's parachain, which
+//!      - `BuyExecution` and `DepositAsset` to the beneficiary
+//!
+//! When the builder lives on AssetHub itself, the reserve deposits directly.
+//! The destination's fee budget and weight limit apply to the final
+//! `BuyExecution`, where the builder is paid.
+
+use crate::ss58::Beneficiary;
+use ink::prelude::{vec, vec::Vec};
+use scale::{Decode, Encode};
+use xcm::v4::prelude::*;
+use xcm::VersionedXcm;
+
+/// `pallet-assets` instance index on AssetHub
+pub const ASSETS_PALLET_INDEX: u8 = 50;
+
+/// Weight limit for `BuyExecution` on a destination
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+pub struct XcmWeight {
+    pub ref_time: u64,
+    pub proof_size: u64,
+}
+
+/// Everything needed to build a donation transfer program
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReserveTransfer {
+    /// AssetHub (reserve) parachain ID
+    pub asset_hub_id: u32,
+    /// Asset ID in AssetHub's `pallet-assets`
+    pub asset_id: u32,
+    /// Amount in the asset's smallest unit
+    pub amount: u128,
+    /// Parachain the builder receives funds on
+    pub dest_para_id: u32,
+    /// Builder's account on the destination parachain
+    pub beneficiary: Beneficiary,
+    /// Most of the donation to spend on execution at the destination (`None` = all of it)
+    pub fee_budget: Option<u128>,
+    /// Weight limit at the destination (`None` = unlimited)
+    pub weight_limit: Option<XcmWeight>,
+}
+
+impl ReserveTransfer {
+    /// Asset as seen from a sibling parachain
+    fn sibling_asset(&self, amount: u128) -> Asset {
+        let location = Location::new(
+            1,
+            [
+                Parachain(self.asset_hub_id),
+                PalletInstance(ASSETS_PALLET_INDEX),
+                GeneralIndex(self.asset_id as u128),
+            ],
+        );
+        (location, amount).into()
+    }
+
+    /// Asset as seen from AssetHub itself
+    fn reserve_local_asset(&self, amount: u128) -> Asset {
+        let location = Location::new(
+            0,
+            [PalletInstance(ASSETS_PALLET_INDEX), GeneralIndex(self.asset_id as u128)],
+        );
+        (location, amount).into()
+    }
+
+    /// Amount offered for execution at the destination
+    fn dest_fees(&self) -> u128 {
+        self.fee_budget.map_or(self.amount, |budget| budget.min(self.amount))
+    }
+
+    fn dest_weight_limit(&self) -> WeightLimit {
+        match self.weight_limit {
+            Some(weight) => Limited(Weight::from_parts(weight.ref_time, weight.proof_size)),
+            None => Unlimited,
+        }
+    }
+
+    fn beneficiary(&self) -> Location {
+        match self.beneficiary {
+            Beneficiary::AccountId32(id) => Location::new(0, [AccountId32 { network: None, id }]),
+            Beneficiary::AccountKey20(key) => Location::new(0, [AccountKey20 { network: None, key }]),
+        }
+    }
+
+    /// Build the versioned program to execute on the contract's chain
+    pub fn build(&self) -> VersionedXcm<()> {
+        VersionedXcm::V4(self.program())
+    }
+
+    /// Build the program to execute on the contract's chain
+    pub fn program(&self) -> Xcm<()> {
+        let deposit = DepositAsset {
+            assets: Wild(AllCounted(1)),
+            beneficiary: self.beneficiary(),
+        };
+
+        let on_reserve = if self.dest_para_id == self.asset_hub_id {
+            Xcm(vec![
+                BuyExecution {
+                    fees: self.reserve_local_asset(self.dest_fees()),
+                    weight_limit: self.dest_weight_limit(),
+                },
+                deposit,
+            ])
+        } else {
+            Xcm(vec![
+                BuyExecution { fees: self.reserve_local_asset(self.amount), weight_limit: Unlimited },
+                DepositReserveAsset {
+                    assets: Wild(AllCounted(1)),
+                    dest: Location::new(1, [Parachain(self.dest_para_id)]),
+                    xcm: Xcm(vec![
+                        BuyExecution {
+                            fees: self.sibling_asset(self.dest_fees()),
+                            weight_limit: self.dest_weight_limit(),
+                        },
+                        deposit,
+                    ]),
+                },
+            ])
+        };
+
+        Xcm(vec![
+            WithdrawAsset(self.sibling_asset(self.amount).into()),
+            InitiateReserveWithdraw {
+                assets: Wild(AllCounted(1)),
+                reserve: Location::new(1, [Parachain(self.asset_hub_id)]),
+                xcm: on_reserve,
+            },
+        ])
+    }
+}
+
+/// Instructions a program makes each remote parachain execute, as
+/// `(para_id, count)` in hop order.
+///
+/// Counts include the two instructions the sending executor prepends
+/// (`WithdrawAsset` or `ReserveAssetDeposited`, then `ClearOrigin`).
+pub fn remote_instruction_counts(program: &Xcm<()>) -> Vec<(u32, u32)> {
+    let mut hops = Vec::new();
+    collect_hops(program, &mut hops);
+    hops
+}
+
+fn collect_hops(program: &Xcm<()>, hops: &mut Vec<(u32, u32)>) {
+    for instruction in program.inner() {
+        let (dest, xcm) = match instruction {
+            InitiateReserveWithdraw { reserve, xcm, .. } => (reserve, xcm),
+            DepositReserveAsset { dest, xcm, .. } => (dest, xcm),
+            _ => continue,
+        };
+        if let (1, [Parachain(para_id)]) = dest.unpack() {
+            hops.push((*para_id, xcm.len() as u32 + 2));
+        }
+        collect_hops(xcm, hops);
+    }
+}
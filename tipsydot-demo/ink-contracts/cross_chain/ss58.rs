@@ -0,0 +1,184 @@
+//! SS58 / AccountKey20 address parsing and validation
+//!
+//! See <https://docs.substrate.io/reference/address-formats/>.
+
+use blake2::{Blake2b512, Digest};
+use ink::prelude::vec::Vec;
+use scale::{Decode, Encode};
+
+const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Prefix hashed in front of the payload when computing SS58 checksums
+const CHECKSUM_PREFIX: &[u8] = b"SS58PRE";
+
+/// Generic Substrate prefix used by dev and test networks, accepted only
+/// where a parachain's format opts in with `accepts_generic`
+pub const GENERIC_SUBSTRATE_PREFIX: u16 = 42;
+
+/// Decoded account that receives funds on the destination parachain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+pub enum Beneficiary {
+    /// Substrate account (`Junction::AccountId32`)
+    AccountId32([u8; 32]),
+    /// Ethereum-style account on EVM parachains (`Junction::AccountKey20`)
+    AccountKey20([u8; 20]),
+}
+
+/// Account format a parachain expects
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+pub enum AccountFormat {
+    /// SS58 addresses with the given network prefix, and also the generic
+    /// prefix 42 when `accepts_generic` is set (dev and test networks)
+    AccountId32 { prefix: u16, accepts_generic: bool },
+    /// `0x`-prefixed 20-byte hex addresses
+    AccountKey20,
+}
+
+/// Why an address was rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressError {
+    /// Not valid base58/hex, wrong length or bad checksum
+    Invalid,
+    /// Valid address, but not for the target parachain
+    PrefixMismatch,
+}
+
+impl AccountFormat {
+    /// The same format, also admitting generic-prefix addresses
+    pub fn with_generic_prefix(self) -> Self {
+        match self {
+            AccountFormat::AccountId32 { prefix, .. } => AccountFormat::AccountId32 { prefix, accepts_generic: true },
+            AccountFormat::AccountKey20 => AccountFormat::AccountKey20,
+        }
+    }
+}
+
+/// Account format of well-known parachains
+pub fn known_format(para_id: u32) -> Option<AccountFormat> {
+    let ss58 = |prefix| Some(AccountFormat::AccountId32 { prefix, accepts_generic: false });
+    match para_id {
+        1000 => ss58(0),                           // AssetHub
+        2000 => ss58(10),                          // Acala
+        2004 => Some(AccountFormat::AccountKey20), // Moonbeam
+        2006 => ss58(5),                           // Astar
+        2034 => ss58(63),                          // Hydration
+        _ => None,
+    }
+}
+
+/// Decode a base58 (Bitcoin alphabet) string
+pub fn base58_decode(input: &str) -> Option<Vec<u8>> {
+    // Little-endian accumulator, reversed at the end
+    let mut bytes: Vec<u8> = Vec::with_capacity(input.len());
+
+    for c in input.bytes() {
+        let mut carry = ALPHABET.iter().position(|&a| a == c)? as u32;
+        for byte in bytes.iter_mut() {
+            carry += (*byte as u32) * 58;
+            *byte = (carry & 0xff) as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push((carry & 0xff) as u8);
+            carry >>= 8;
+        }
+    }
+
+    // Each leading '1' encodes a leading zero byte
+    let zeros = input.bytes().take_while(|&c| c == b'1').count();
+    bytes.resize(bytes.len() + zeros, 0);
+
+    bytes.reverse();
+    Some(bytes)
+}
+
+/// Decode and checksum-verify an SS58 address into `(prefix, account)`
+pub fn decode(address: &str) -> Result<(u16, [u8; 32]), AddressError> {
+    let bytes = base58_decode(address).ok_or(AddressError::Invalid)?;
+
+    let (prefix, prefix_len) = match bytes.first() {
+        Some(&first) if first < 64 => (first as u16, 1),
+        Some(&first) if first < 128 && bytes.len() > 1 => {
+            let second = bytes[1];
+            let lower = ((first & 0x3f) << 2) | (second >> 6);
+            let upper = second & 0x3f;
+            ((lower as u16) | ((upper as u16) << 8), 2)
+        }
+        _ => return Err(AddressError::Invalid),
+    };
+
+    if bytes.len() != prefix_len + 32 + 2 {
+        return Err(AddressError::Invalid);
+    }
+
+    let payload = &bytes[..prefix_len + 32];
+    let hash = Blake2b512::new()
+        .chain_update(CHECKSUM_PREFIX)
+        .chain_update(payload)
+        .finalize();
+    if hash[..2] != bytes[prefix_len + 32..] {
+        return Err(AddressError::Invalid);
+    }
+
+    let mut account = [0u8; 32];
+    account.copy_from_slice(&bytes[prefix_len..prefix_len + 32]);
+    Ok((prefix, account))
+}
+
+/// Parse a `0x`-prefixed 20-byte hex address
+pub fn decode_key20(address: &str) -> Result<[u8; 20], AddressError> {
+    let hex = address
+        .strip_prefix("0x")
+        .or_else(|| address.strip_prefix("0X"))
+        .ok_or(AddressError::Invalid)?
+        .as_bytes();
+
+    if hex.len() != 40 {
+        return Err(AddressError::Invalid);
+    }
+
+    let nibble = |c: u8| match c {
+        b'0'..=b'9' => Ok(c - b'0'),
+        b'a'..=b'f' => Ok(c - b'a' + 10),
+        b'A'..=b'F' => Ok(c - b'A' + 10),
+        _ => Err(AddressError::Invalid),
+    };
+
+    let mut key = [0u8; 20];
+    for (i, byte) in key.iter_mut().enumerate() {
+        *byte = (nibble(hex[2 * i])? << 4) | nibble(hex[2 * i + 1])?;
+    }
+    Ok(key)
+}
+
+/// Validate a builder address against the format of its parachain
+///
+/// Unknown parachains accept any valid SS58 or 20-byte hex address.
+pub fn parse_beneficiary(address: &str, format: Option<AccountFormat>) -> Result<Beneficiary, AddressError> {
+    let is_hex = address.starts_with("0x") || address.starts_with("0X");
+
+    match (format, is_hex) {
+        (Some(AccountFormat::AccountKey20), true) | (None, true) => {
+            decode_key20(address).map(Beneficiary::AccountKey20)
+        }
+        (Some(AccountFormat::AccountKey20), false) => {
+            // Surface typos as `Invalid`, well-formed SS58 as the wrong format
+            decode(address)?;
+            Err(AddressError::PrefixMismatch)
+        }
+        (Some(AccountFormat::AccountId32 { .. }), true) => {
+            decode_key20(address)?;
+            Err(AddressError::PrefixMismatch)
+        }
+        (Some(AccountFormat::AccountId32 { prefix: expected, accepts_generic }), false) => {
+            let (prefix, account) = decode(address)?;
+            if prefix != expected && !(accepts_generic && prefix == GENERIC_SUBSTRATE_PREFIX) {
+                return Err(AddressError::PrefixMismatch);
+            }
+            Ok(Beneficiary::AccountId32(account))
+        }
+        (None, false) => decode(address).map(|(_, account)| Beneficiary::AccountId32(account)),
+    }
+}
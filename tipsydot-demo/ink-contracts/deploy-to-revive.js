@@ -32,7 +32,7 @@ const CONTRACTS = {
         wasm: './target/ink/cross_chain.contract',
         metadata: './target/ink/cross_chain.json',
         constructor: 'new',
-        args: [1000, 1337], // AssetHub ID, USDC Asset ID
+        args: [], // Will be set after USDC deployment
     },
 };
 
@@ -61,6 +61,16 @@ async function deployInkContract(api, deployer, contractInfo, previousContracts
         ];
     }
 
+    // Cross-chain donations escrow the same USDC token
+    if (contractInfo.name === 'cross_chain' && previousContracts.psp22_usdc) {
+        constructorArgs = [
+            1000,                           // AssetHub parachain ID
+            1337,                           // USDC asset ID on AssetHub
+            previousContracts.psp22_usdc,   // USDC token address
+            deployer.address,               // XCM reserve, funds the AssetHub balance
+        ];
+    }
+
     // Estimate gas
     const gasLimit = api.registry.createType('WeightV2', {
         refTime: 10000000000,
@@ -260,7 +270,8 @@ async function main() {
         deployedContracts.cross_chain = await deployInkContract(
             api,
             deployer,
-            { ...CONTRACTS.cross_chain, name: 'cross_chain' }
+            { ...CONTRACTS.cross_chain, name: 'cross_chain' },
+            deployedContracts
         );
 
         // Deploy Solidity contracts (if compiled for PolkaVM)
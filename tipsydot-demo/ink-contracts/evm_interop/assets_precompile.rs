@@ -0,0 +1,130 @@
+//! pallet-assets ERC20 precompile addressing and ABI encoding
+//!
+//! pallet-revive exposes every asset as an ERC20 contract at an address
+//! derived from its ID, e.g. `0x0000053900000000000000000000000001200000`
+//! for asset 1337, so assets can be moved without a wrapper contract.
+
+use crate::{to_address, ETH_ACCOUNT_SUFFIX};
+use ink::prelude::vec::Vec;
+
+/// Last four bytes of every assets precompile address
+pub const ADDRESS_SUFFIX: [u8; 4] = [0x01, 0x20, 0x00, 0x00];
+
+/// `transfer(address,uint256)`
+pub const TRANSFER: [u8; 4] = [0xa9, 0x05, 0x9c, 0xbb];
+/// `transferFrom(address,address,uint256)`
+pub const TRANSFER_FROM: [u8; 4] = [0x23, 0xb8, 0x72, 0xdd];
+/// `balanceOf(address)`
+pub const BALANCE_OF: [u8; 4] = [0x70, 0xa0, 0x82, 0x31];
+
+/// Precompile address of `asset_id`
+pub fn address(asset_id: u32) -> [u8; 20] {
+    let mut address = [0u8; 20];
+    address[..4].copy_from_slice(&asset_id.to_be_bytes());
+    address[16..].copy_from_slice(&ADDRESS_SUFFIX);
+    address
+}
+
+/// Account the precompile of `asset_id` is reachable at
+pub fn account(asset_id: u32) -> [u8; 32] {
+    let mut account = [0u8; 32];
+    account[..20].copy_from_slice(&address(asset_id));
+    account[20..].copy_from_slice(&ETH_ACCOUNT_SUFFIX);
+    account
+}
+
+/// Asset ID behind `account`, if it is an assets precompile
+pub fn asset_id(account: &[u8; 32]) -> Option<u32> {
+    if account[20..] != ETH_ACCOUNT_SUFFIX {
+        return None;
+    }
+    address_asset_id(&account[..20].try_into().ok()?)
+}
+
+/// Asset ID behind `address`, if it is an assets precompile
+///
+/// `0x<asset id: 4 bytes>` followed by zeros and the `0x0120` prefix,
+/// e.g. `0x000007c000000000000000000000000001200000` for asset 1984.
+pub fn address_asset_id(address: &[u8; 20]) -> Option<u32> {
+    if address[4..16] != [0u8; 12] || address[16..] != ADDRESS_SUFFIX {
+        return None;
+    }
+    Some(u32::from_be_bytes([address[0], address[1], address[2], address[3]]))
+}
+
+/// ABI `address` argument for `account`, as the precompile sees it
+pub fn address_word(account: &[u8; 32]) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[12..].copy_from_slice(to_address(account).as_bytes());
+    word
+}
+
+/// ABI `uint256` argument
+pub fn uint_word(value: u128) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[16..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+/// Arguments of `transferFrom(from, to, amount)`, without the selector
+pub fn transfer_from_args(from: &[u8; 32], to: &[u8; 32], amount: u128) -> Vec<u8> {
+    [address_word(from), address_word(to), uint_word(amount)].concat()
+}
+
+/// Arguments of `transfer(to, amount)`, without the selector
+pub fn transfer_args(to: &[u8; 32], amount: u128) -> Vec<u8> {
+    [address_word(to), uint_word(amount)].concat()
+}
+
+/// Whether an ERC20 call's return data reports success
+///
+/// An ABI `true`, or no data at all for tokens that signal success by
+/// not reverting.
+pub fn is_success(output: &[u8]) -> bool {
+    match output.len() {
+        0 => true,
+        32 => output[..31] == [0u8; 31] && output[31] == 1,
+        _ => false,
+    }
+}
+
+/// Decode a `uint256` return that fits a `u128`
+pub fn decode_uint(output: &[u8]) -> Option<u128> {
+    if output.len() != 32 || output[..16] != [0u8; 16] {
+        return None;
+    }
+    let mut value = [0u8; 16];
+    value.copy_from_slice(&output[16..]);
+    Some(u128::from_be_bytes(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn precompile_addresses_round_trip() {
+        assert_eq!(address(1337), [0, 0, 0x05, 0x39, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x01, 0x20, 0, 0]);
+        assert_eq!(address_asset_id(&address(1984)), Some(1984));
+        assert_eq!(asset_id(&account(1984)), Some(1984));
+        assert_eq!(address_asset_id(&[0x01; 20]), None);
+
+        // The address alone is not the precompile's account
+        let mut native = [0u8; 32];
+        native[..20].copy_from_slice(&address(1984));
+        assert_eq!(asset_id(&native), None);
+    }
+
+    #[test]
+    fn erc20_returns_are_read_as_raw_abi() {
+        // An ABI `true` and a balance both start with zero bytes
+        assert!(is_success(&uint_word(1)));
+        assert!(is_success(&[]));
+        assert!(!is_success(&uint_word(0)));
+        assert!(!is_success(&uint_word(1)[1..]));
+
+        assert_eq!(decode_uint(&uint_word(25_000_000)), Some(25_000_000));
+        assert_eq!(decode_uint(&uint_word(25_000_000)[1..]), None);
+        assert_eq!(decode_uint(&[0xff; 32]), None);
+    }
+}
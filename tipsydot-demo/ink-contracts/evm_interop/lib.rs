@@ -0,0 +1,151 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Shared helpers for contracts that keep 32-byte accounts but call into
+//! pallet-revive's H160 world, like the `psp22_usdc` token or Solidity
+//! contracts speaking the raw Ethereum ABI.
+
+use ink::env::hash::{HashOutput, Keccak256};
+use ink::env::ReturnErrorCode;
+use ink::prelude::vec::Vec;
+use ink::primitives::H160;
+use scale::{Decode, Encode};
+
+pub mod assets_precompile;
+
+/// Bytes pallet-revive appends to an Ethereum address to form its account
+pub const ETH_ACCOUNT_SUFFIX: [u8; 12] = [0xEE; 12];
+
+/// Address pallet-revive maps `account` to
+///
+/// Accounts derived from an Ethereum key (contracts included) carry their
+/// address followed by twelve `0xEE` bytes; all others use the last 20
+/// bytes of their Keccak-256 hash.
+pub fn to_address(account: &[u8; 32]) -> H160 {
+    if account[20..] == ETH_ACCOUNT_SUFFIX {
+        return H160::from_slice(&account[..20]);
+    }
+
+    let mut hash = <Keccak256 as HashOutput>::Type::default();
+    ink::env::hash_bytes::<Keccak256>(account, &mut hash);
+    H160::from_slice(&hash[12..])
+}
+
+/// Account pallet-revive maps `address` to
+pub fn to_account(address: H160) -> [u8; 32] {
+    let mut account = [0u8; 32];
+    account[..20].copy_from_slice(address.as_bytes());
+    account[20..].copy_from_slice(&ETH_ACCOUNT_SUFFIX);
+    account
+}
+
+/// Bytes passed through without a SCALE length prefix
+///
+/// Encodes as the bytes themselves and decodes everything that is left,
+/// which is how raw Solidity calldata travels.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawBytes(pub Vec<u8>);
+
+impl Encode for RawBytes {
+    fn size_hint(&self) -> usize {
+        self.0.len()
+    }
+
+    fn encode_to<T: scale::Output + ?Sized>(&self, dest: &mut T) {
+        dest.write(&self.0)
+    }
+}
+
+impl Decode for RawBytes {
+    fn decode<I: scale::Input>(input: &mut I) -> Result<Self, scale::Error> {
+        let mut bytes = Vec::new();
+        while let Ok(byte) = input.read_byte() {
+            bytes.push(byte);
+        }
+        Ok(RawBytes(bytes))
+    }
+}
+
+/// Send ABI `calldata` to `callee` verbatim
+///
+/// Returns the output on success or the revert data on failure, which is
+/// empty if the call could not be made at all. Ink's call builder decodes
+/// every output as a SCALE `MessageResult` and doesn't say whether the callee
+/// reverted, so the call goes straight to pallet-revive.
+#[cfg(target_arch = "riscv64")]
+pub fn raw_call(callee: H160, calldata: &[u8]) -> Result<Vec<u8>, Vec<u8>> {
+    use pallet_revive_uapi::{CallFlags, HostFn, HostFnImpl as api};
+
+    let result = api::call(
+        CallFlags::empty(),
+        &callee.0,
+        u64::MAX,
+        u64::MAX,
+        &[0xff; 32],
+        &[0; 32],
+        calldata,
+        None,
+    );
+
+    let mut output = ink::prelude::vec![0u8; api::return_data_size() as usize];
+    api::return_data_copy(&mut &mut output[..], 0);
+    call_outcome(result, output)
+}
+
+/// The off-chain environment can't call other contracts
+#[cfg(not(target_arch = "riscv64"))]
+pub fn raw_call(_callee: H160, _calldata: &[u8]) -> Result<Vec<u8>, Vec<u8>> {
+    Err(Vec::new())
+}
+
+/// Sort a finished call's output into returned data and revert data
+#[cfg_attr(not(target_arch = "riscv64"), allow(dead_code))]
+fn call_outcome(result: Result<(), ReturnErrorCode>, output: Vec<u8>) -> Result<Vec<u8>, Vec<u8>> {
+    match result {
+        Ok(()) => Ok(output),
+        Err(ReturnErrorCode::CalleeReverted) => Err(output),
+        Err(_) => Err(Vec::new()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ethereum_accounts_map_back_to_their_address() {
+        let address = H160::from([0x11; 20]);
+        let account = to_account(address);
+        assert_eq!(account[20..], [0xEE; 12]);
+        assert_eq!(to_address(&account), address);
+    }
+
+    #[test]
+    fn native_accounts_map_to_their_hash() {
+        let native = to_address(&[0x01; 32]);
+        assert_ne!(native, H160::from([0x01; 20]));
+        assert_eq!(native, to_address(&[0x01; 32]));
+        assert_ne!(native, to_address(&[0x02; 32]));
+    }
+
+    #[test]
+    fn raw_bytes_travel_without_a_length_prefix() {
+        let calldata = vec![0xa9, 0x05, 0x9c, 0xbb, 0x01];
+        assert_eq!(RawBytes(calldata.clone()).encode(), calldata);
+        assert_eq!(RawBytes::decode(&mut &calldata[..]), Ok(RawBytes(calldata)));
+    }
+
+    #[test]
+    fn call_outcome_keeps_the_whole_buffer() {
+        // An ABI `true` starts with a zero byte a `MessageResult` would eat
+        let mut word = [0u8; 32];
+        word[31] = 1;
+        assert_eq!(call_outcome(Ok(()), word.to_vec()), Ok(word.to_vec()));
+
+        // Revert data is kept for the caller to explain the failure
+        let revert = vec![0x08, 0xc3, 0x79, 0xa0, 0x00];
+        assert_eq!(call_outcome(Err(ReturnErrorCode::CalleeReverted), revert.clone()), Err(revert));
+
+        // A call that never ran has nothing to explain
+        assert_eq!(call_outcome(Err(ReturnErrorCode::CalleeTrapped), vec![0x01]), Err(Vec::new()));
+    }
+}
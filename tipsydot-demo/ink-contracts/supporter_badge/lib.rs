@@ -0,0 +1,653 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+use ink::prelude::{string::String, vec::Vec};
+use ink::primitives::H160;
+use scale::{Decode, Encode};
+
+/// PSP34 token identifier
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+pub enum Id {
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    Bytes(Vec<u8>),
+}
+
+/// What a supporter did to earn a badge
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+pub enum BadgeKind {
+    /// First tip to a builder
+    FirstTip,
+    /// Cumulative tips to a builder crossed a configured tier
+    AmountTier,
+    /// Contributed to a builder's campaign
+    CampaignContributor,
+}
+
+/// A minted supporter badge
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+pub struct Badge {
+    pub kind: BadgeKind,
+    pub builder_id: u32,
+    pub campaign_id: Option<u32>,
+    /// 1-based tier for `AmountTier`, 0 otherwise
+    pub tier: u8,
+    /// Tipping contract that awarded the badge
+    pub issuer: H160,
+    pub minted_at: u64,
+}
+
+/// PSP34 standard errors
+#[derive(Debug, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PSP34Error {
+    Custom(String),
+    SelfApprove,
+    NotApproved,
+    TokenExists,
+    TokenNotExists,
+    SafeTransferCheckFailed(String),
+}
+
+/// Errors for badge administration and minting
+#[derive(Debug, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum BadgeError {
+    Unauthorized,
+    AlreadyAwarded,
+}
+
+/// PSP34 (NFT) standard interface
+#[ink::trait_definition]
+pub trait PSP34 {
+    #[ink(message)]
+    fn collection_id(&self) -> Id;
+
+    #[ink(message)]
+    fn balance_of(&self, owner: H160) -> u32;
+
+    #[ink(message)]
+    fn owner_of(&self, id: Id) -> Option<H160>;
+
+    #[ink(message)]
+    fn allowance(&self, owner: H160, operator: H160, id: Option<Id>) -> bool;
+
+    #[ink(message)]
+    fn approve(&mut self, operator: H160, id: Option<Id>, approved: bool) -> Result<(), PSP34Error>;
+
+    #[ink(message)]
+    fn transfer(&mut self, to: H160, id: Id, data: Vec<u8>) -> Result<(), PSP34Error>;
+
+    #[ink(message)]
+    fn total_supply(&self) -> u128;
+}
+
+/// PSP34 metadata extension
+#[ink::trait_definition]
+pub trait PSP34Metadata {
+    #[ink(message)]
+    fn get_attribute(&self, id: Id, key: Vec<u8>) -> Option<Vec<u8>>;
+}
+
+/// Interface used by the tipping contracts to award badges
+#[ink::trait_definition]
+pub trait BadgeMinter {
+    #[ink(message)]
+    fn mint_badge(
+        &mut self,
+        to: H160,
+        kind: BadgeKind,
+        builder_id: u32,
+        campaign_id: Option<u32>,
+        tier: u8,
+    ) -> Result<Id, BadgeError>;
+}
+
+/// Call `BadgeMinter::mint_badge` on `badge_contract`
+///
+/// Returns the new badge's ID, or `None` if the call failed or the badge
+/// contract refused to mint, so tipping contracts can award badges without
+/// a failing badge contract ever blocking a tip.
+pub fn try_mint_badge(
+    badge_contract: H160,
+    to: H160,
+    kind: BadgeKind,
+    builder_id: u32,
+    campaign_id: Option<u32>,
+    tier: u8,
+) -> Option<Id> {
+    use ink::env::call::{build_call, ExecutionInput, Selector};
+
+    build_call::<ink::env::DefaultEnvironment>()
+        .call(badge_contract)
+        .exec_input(
+            ExecutionInput::new(Selector::new(ink::selector_bytes!("BadgeMinter::mint_badge")))
+                .push_arg(to)
+                .push_arg(kind)
+                .push_arg(builder_id)
+                .push_arg(campaign_id)
+                .push_arg(tier)
+        )
+        .returns::<Result<Id, BadgeError>>()
+        .try_invoke()
+        .ok()
+        .and_then(Result::ok)
+        .and_then(Result::ok)
+}
+
+/// Supporter badge collection
+///
+/// Badges are minted by authorised tipping contracts when a supporter
+/// reaches a milestone, and are non-transferable unless the owner
+/// explicitly enables transfers.
+#[ink::contract]
+pub mod supporter_badge {
+    use super::*;
+    use ink::prelude::format;
+    use ink::storage::Mapping;
+
+    #[ink(event)]
+    pub struct Transfer {
+        #[ink(topic)]
+        from: Option<H160>,
+        #[ink(topic)]
+        to: Option<H160>,
+        #[ink(topic)]
+        id: Id,
+    }
+
+    #[ink(event)]
+    pub struct Approval {
+        #[ink(topic)]
+        owner: H160,
+        #[ink(topic)]
+        operator: H160,
+        #[ink(topic)]
+        id: Option<Id>,
+        approved: bool,
+    }
+
+    #[ink(event)]
+    pub struct BadgeMinted {
+        #[ink(topic)]
+        to: H160,
+        #[ink(topic)]
+        builder_id: u32,
+        badge_id: u128,
+        kind: BadgeKind,
+        campaign_id: Option<u32>,
+        tier: u8,
+    }
+
+    #[ink(event)]
+    pub struct MinterUpdated {
+        #[ink(topic)]
+        minter: H160,
+        allowed: bool,
+    }
+
+    /// Key preventing the same milestone from being awarded twice
+    type AwardKey = (H160, H160, BadgeKind, u32, Option<u32>, u8);
+
+    #[ink(storage)]
+    pub struct SupporterBadge {
+        /// Contract owner
+        owner: H160,
+        /// Contracts allowed to mint badges
+        minters: Mapping<H160, bool>,
+        /// Badge details by token ID
+        badges: Mapping<u128, Badge>,
+        /// Token owners
+        token_owner: Mapping<u128, H160>,
+        /// Number of badges held per account
+        owned_count: Mapping<H160, u32>,
+        /// Single-token approvals
+        token_approvals: Mapping<u128, H160>,
+        /// Operator approvals for all tokens of an owner
+        operator_approvals: Mapping<(H160, H160), bool>,
+        /// Milestones already awarded
+        awarded: Mapping<AwardKey, u128>,
+        /// Number of badges minted (also the last token ID)
+        total_minted: u128,
+        /// Prefix for badge metadata URIs
+        base_uri: String,
+        /// Whether holders may transfer badges
+        transferable: bool,
+    }
+
+    impl SupporterBadge {
+        /// Creates a new badge collection
+        #[ink(constructor)]
+        pub fn new(base_uri: String) -> Self {
+            Self {
+                owner: Self::env().caller(),
+                minters: Mapping::new(),
+                badges: Mapping::new(),
+                token_owner: Mapping::new(),
+                owned_count: Mapping::new(),
+                token_approvals: Mapping::new(),
+                operator_approvals: Mapping::new(),
+                awarded: Mapping::new(),
+                total_minted: 0,
+                base_uri,
+                transferable: false,
+            }
+        }
+
+        /// Allow or revoke a minter (only owner)
+        #[ink(message)]
+        pub fn set_minter(&mut self, minter: H160, allowed: bool) -> Result<(), BadgeError> {
+            self.ensure_owner()?;
+
+            if allowed {
+                self.minters.insert(minter, &true);
+            } else {
+                self.minters.remove(minter);
+            }
+
+            self.env().emit_event(MinterUpdated { minter, allowed });
+            Ok(())
+        }
+
+        /// Enable or disable badge transfers (only owner)
+        #[ink(message)]
+        pub fn set_transferable(&mut self, transferable: bool) -> Result<(), BadgeError> {
+            self.ensure_owner()?;
+            self.transferable = transferable;
+            Ok(())
+        }
+
+        /// Update the metadata URI prefix (only owner)
+        #[ink(message)]
+        pub fn set_base_uri(&mut self, base_uri: String) -> Result<(), BadgeError> {
+            self.ensure_owner()?;
+            self.base_uri = base_uri;
+            Ok(())
+        }
+
+        /// Check if an account may mint badges
+        #[ink(message)]
+        pub fn is_minter(&self, account: H160) -> bool {
+            self.minters.get(account).unwrap_or(false)
+        }
+
+        /// Check if badges can be transferred
+        #[ink(message)]
+        pub fn is_transferable(&self) -> bool {
+            self.transferable
+        }
+
+        /// Get badge details
+        #[ink(message)]
+        pub fn get_badge(&self, badge_id: u128) -> Option<Badge> {
+            self.badges.get(badge_id)
+        }
+
+        /// Check whether a milestone badge was already awarded
+        #[ink(message)]
+        pub fn has_badge(
+            &self,
+            owner: H160,
+            issuer: H160,
+            kind: BadgeKind,
+            builder_id: u32,
+            campaign_id: Option<u32>,
+            tier: u8,
+        ) -> bool {
+            self.awarded
+                .contains((owner, issuer, kind, builder_id, campaign_id, tier))
+        }
+
+        /// Metadata URI of a badge: `<base>/builder/<id>/<kind>/<tier>`
+        #[ink(message)]
+        pub fn token_uri(&self, badge_id: u128) -> Option<String> {
+            let badge = self.badges.get(badge_id)?;
+            let path = match badge.campaign_id {
+                Some(campaign_id) => format!(
+                    "builder/{}/campaign/{}",
+                    badge.builder_id, campaign_id
+                ),
+                None => format!(
+                    "builder/{}/{}/{}",
+                    badge.builder_id,
+                    Self::kind_name(badge.kind),
+                    badge.tier
+                ),
+            };
+            Some(format!("{}/{}", self.base_uri.trim_end_matches('/'), path))
+        }
+
+        fn kind_name(kind: BadgeKind) -> &'static str {
+            match kind {
+                BadgeKind::FirstTip => "first-tip",
+                BadgeKind::AmountTier => "amount-tier",
+                BadgeKind::CampaignContributor => "campaign",
+            }
+        }
+
+        fn badge_id(id: &Id) -> Option<u128> {
+            match id {
+                Id::U128(value) => Some(*value),
+                _ => None,
+            }
+        }
+
+        fn is_approved_or_owner(&self, owner: H160, operator: H160, badge_id: u128) -> bool {
+            owner == operator
+                || self.token_approvals.get(badge_id) == Some(operator)
+                || self.operator_approvals.get((owner, operator)).unwrap_or(false)
+        }
+
+        /// Helper: ensure caller is owner
+        fn ensure_owner(&self) -> Result<(), BadgeError> {
+            if self.env().caller() != self.owner {
+                return Err(BadgeError::Unauthorized);
+            }
+            Ok(())
+        }
+    }
+
+    impl PSP34 for SupporterBadge {
+        #[ink(message)]
+        fn collection_id(&self) -> Id {
+            Id::Bytes(self.env().address().as_bytes().to_vec())
+        }
+
+        #[ink(message)]
+        fn balance_of(&self, owner: H160) -> u32 {
+            self.owned_count.get(owner).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        fn owner_of(&self, id: Id) -> Option<H160> {
+            Self::badge_id(&id).and_then(|badge_id| self.token_owner.get(badge_id))
+        }
+
+        #[ink(message)]
+        fn allowance(&self, owner: H160, operator: H160, id: Option<Id>) -> bool {
+            if self.operator_approvals.get((owner, operator)).unwrap_or(false) {
+                return true;
+            }
+            match id.as_ref().and_then(Self::badge_id) {
+                Some(badge_id) => self.token_approvals.get(badge_id) == Some(operator),
+                None => false,
+            }
+        }
+
+        #[ink(message)]
+        fn approve(&mut self, operator: H160, id: Option<Id>, approved: bool) -> Result<(), PSP34Error> {
+            let caller = self.env().caller();
+            if operator == caller {
+                return Err(PSP34Error::SelfApprove);
+            }
+
+            match &id {
+                Some(id) => {
+                    let badge_id = Self::badge_id(id).ok_or(PSP34Error::TokenNotExists)?;
+                    let owner = self.token_owner.get(badge_id).ok_or(PSP34Error::TokenNotExists)?;
+                    if owner != caller {
+                        return Err(PSP34Error::NotApproved);
+                    }
+                    if approved {
+                        self.token_approvals.insert(badge_id, &operator);
+                    } else {
+                        self.token_approvals.remove(badge_id);
+                    }
+                }
+                None => {
+                    if approved {
+                        self.operator_approvals.insert((caller, operator), &true);
+                    } else {
+                        self.operator_approvals.remove((caller, operator));
+                    }
+                }
+            }
+
+            self.env().emit_event(Approval {
+                owner: caller,
+                operator,
+                id,
+                approved,
+            });
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn transfer(&mut self, to: H160, id: Id, _data: Vec<u8>) -> Result<(), PSP34Error> {
+            if !self.transferable {
+                return Err(PSP34Error::Custom(String::from("NonTransferable")));
+            }
+
+            let badge_id = Self::badge_id(&id).ok_or(PSP34Error::TokenNotExists)?;
+            let owner = self.token_owner.get(badge_id).ok_or(PSP34Error::TokenNotExists)?;
+
+            let caller = self.env().caller();
+            if !self.is_approved_or_owner(owner, caller, badge_id) {
+                return Err(PSP34Error::NotApproved);
+            }
+
+            self.token_approvals.remove(badge_id);
+            self.owned_count.insert(owner, &(self.balance_of(owner) - 1));
+            self.owned_count.insert(to, &(self.balance_of(to) + 1));
+            self.token_owner.insert(badge_id, &to);
+
+            self.env().emit_event(Transfer {
+                from: Some(owner),
+                to: Some(to),
+                id,
+            });
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn total_supply(&self) -> u128 {
+            self.total_minted
+        }
+    }
+
+    impl PSP34Metadata for SupporterBadge {
+        /// Supported keys: `builder_id`, `kind`, `tier`, `campaign_id`, `uri`
+        #[ink(message)]
+        fn get_attribute(&self, id: Id, key: Vec<u8>) -> Option<Vec<u8>> {
+            let badge_id = Self::badge_id(&id)?;
+            let badge = self.badges.get(badge_id)?;
+
+            let value = match key.as_slice() {
+                b"builder_id" => format!("{}", badge.builder_id),
+                b"kind" => String::from(Self::kind_name(badge.kind)),
+                b"tier" => format!("{}", badge.tier),
+                b"campaign_id" => format!("{}", badge.campaign_id?),
+                b"uri" => self.token_uri(badge_id)?,
+                _ => return None,
+            };
+            Some(value.into_bytes())
+        }
+    }
+
+    impl BadgeMinter for SupporterBadge {
+        /// Mint a milestone badge (only minters)
+        #[ink(message)]
+        fn mint_badge(
+            &mut self,
+            to: H160,
+            kind: BadgeKind,
+            builder_id: u32,
+            campaign_id: Option<u32>,
+            tier: u8,
+        ) -> Result<Id, BadgeError> {
+            let issuer = self.env().caller();
+            if !self.is_minter(issuer) {
+                return Err(BadgeError::Unauthorized);
+            }
+
+            let key = (to, issuer, kind, builder_id, campaign_id, tier);
+            if self.awarded.contains(key) {
+                return Err(BadgeError::AlreadyAwarded);
+            }
+
+            self.total_minted += 1;
+            let badge_id = self.total_minted;
+
+            let badge = Badge {
+                kind,
+                builder_id,
+                campaign_id,
+                tier,
+                issuer,
+                minted_at: self.env().block_timestamp(),
+            };
+
+            self.badges.insert(badge_id, &badge);
+            self.token_owner.insert(badge_id, &to);
+            self.owned_count.insert(to, &(self.balance_of(to) + 1));
+            self.awarded.insert(key, &badge_id);
+
+            self.env().emit_event(Transfer {
+                from: None,
+                to: Some(to),
+                id: Id::U128(badge_id),
+            });
+
+            self.env().emit_event(BadgeMinted {
+                to,
+                builder_id,
+                badge_id,
+                kind,
+                campaign_id,
+                tier,
+            });
+
+            Ok(Id::U128(badge_id))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::test;
+
+        fn default_accounts() -> test::DefaultAccounts {
+            test::default_accounts()
+        }
+
+        /// Alice owns the collection and Charlie acts as the tipping contract
+        fn create_contract() -> SupporterBadge {
+            let accounts = default_accounts();
+            test::set_caller(accounts.alice);
+            let mut contract = SupporterBadge::new("ipfs://tipsydot-badges/".into());
+            contract.set_minter(accounts.charlie, true).unwrap();
+            test::set_caller(accounts.charlie);
+            contract
+        }
+
+        #[ink::test]
+        fn mint_badge_works() {
+            let mut contract = create_contract();
+            let accounts = default_accounts();
+
+            let id = contract.mint_badge(accounts.bob, BadgeKind::FirstTip, 1, None, 0).unwrap();
+            assert_eq!(id, Id::U128(1));
+            assert_eq!(contract.owner_of(id.clone()), Some(accounts.bob));
+            assert_eq!(contract.balance_of(accounts.bob), 1);
+            assert_eq!(contract.total_supply(), 1);
+
+            let badge = contract.get_badge(1).unwrap();
+            assert_eq!(badge.kind, BadgeKind::FirstTip);
+            assert_eq!(badge.builder_id, 1);
+            assert_eq!(badge.issuer, accounts.charlie);
+            assert!(contract.has_badge(accounts.bob, accounts.charlie, BadgeKind::FirstTip, 1, None, 0));
+        }
+
+        #[ink::test]
+        fn mint_badge_only_minters() {
+            let mut contract = create_contract();
+            let accounts = default_accounts();
+
+            test::set_caller(accounts.django);
+            assert_eq!(
+                contract.mint_badge(accounts.bob, BadgeKind::FirstTip, 1, None, 0),
+                Err(BadgeError::Unauthorized)
+            );
+        }
+
+        #[ink::test]
+        fn milestone_awarded_once() {
+            let mut contract = create_contract();
+            let accounts = default_accounts();
+
+            assert!(contract.mint_badge(accounts.bob, BadgeKind::AmountTier, 2, None, 1).is_ok());
+            assert_eq!(
+                contract.mint_badge(accounts.bob, BadgeKind::AmountTier, 2, None, 1),
+                Err(BadgeError::AlreadyAwarded)
+            );
+
+            // A higher tier is a different milestone
+            assert!(contract.mint_badge(accounts.bob, BadgeKind::AmountTier, 2, None, 2).is_ok());
+            assert_eq!(contract.balance_of(accounts.bob), 2);
+        }
+
+        #[ink::test]
+        fn badges_are_non_transferable_by_default() {
+            let mut contract = create_contract();
+            let accounts = default_accounts();
+
+            let id = contract.mint_badge(accounts.bob, BadgeKind::FirstTip, 1, None, 0).unwrap();
+
+            test::set_caller(accounts.bob);
+            assert_eq!(
+                contract.transfer(accounts.eve, id.clone(), Vec::new()),
+                Err(PSP34Error::Custom("NonTransferable".into()))
+            );
+
+            // Owner enables transfers
+            test::set_caller(accounts.alice);
+            contract.set_transferable(true).unwrap();
+
+            test::set_caller(accounts.bob);
+            assert!(contract.transfer(accounts.eve, id.clone(), Vec::new()).is_ok());
+            assert_eq!(contract.owner_of(id), Some(accounts.eve));
+            assert_eq!(contract.balance_of(accounts.bob), 0);
+            assert_eq!(contract.balance_of(accounts.eve), 1);
+        }
+
+        #[ink::test]
+        fn metadata_points_to_builder_and_tier() {
+            let mut contract = create_contract();
+            let accounts = default_accounts();
+
+            let tier = contract.mint_badge(accounts.bob, BadgeKind::AmountTier, 3, None, 2).unwrap();
+            let campaign = contract
+                .mint_badge(accounts.bob, BadgeKind::CampaignContributor, 3, Some(7), 0)
+                .unwrap();
+
+            assert_eq!(contract.get_attribute(tier.clone(), b"builder_id".to_vec()), Some(b"3".to_vec()));
+            assert_eq!(contract.get_attribute(tier.clone(), b"tier".to_vec()), Some(b"2".to_vec()));
+            assert_eq!(contract.get_attribute(tier, b"campaign_id".to_vec()), None);
+            assert_eq!(contract.get_attribute(campaign.clone(), b"campaign_id".to_vec()), Some(b"7".to_vec()));
+
+            assert_eq!(
+                contract.token_uri(1),
+                Some("ipfs://tipsydot-badges/builder/3/amount-tier/2".into())
+            );
+            assert_eq!(
+                contract.token_uri(2),
+                Some("ipfs://tipsydot-badges/builder/3/campaign/7".into())
+            );
+        }
+
+        #[ink::test]
+        fn set_minter_unauthorized() {
+            let mut contract = create_contract();
+            let accounts = default_accounts();
+
+            // Charlie is a minter but not the owner
+            assert_eq!(contract.set_minter(accounts.eve, true), Err(BadgeError::Unauthorized));
+            assert_eq!(contract.set_transferable(true), Err(BadgeError::Unauthorized));
+        }
+    }
+}
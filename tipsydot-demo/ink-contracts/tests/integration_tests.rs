@@ -3,6 +3,7 @@
 
 #[cfg(test)]
 mod integration_tests {
+    use ink::primitives::H160;
     use ink_e2e::ContractsBackend;
     use psp22_usdc::MockUSDC;
     use tipping::Tipping;
@@ -10,6 +11,11 @@ mod integration_tests {
 
     type E2EResult<T> = Result<T, Box<dyn std::error::Error>>;
 
+    /// Address the H160 USDC token sees for a 32-byte account
+    fn eth(account: AccountId) -> H160 {
+        evm_interop::to_address(account.as_ref())
+    }
+
     /// Test the complete tipping flow
     #[ink_e2e::test]
     async fn test_complete_tipping_flow(mut client: Client) -> E2EResult<()> {
@@ -30,14 +36,14 @@ mod integration_tests {
 
         // 3. Mint USDC to Bob (tipper)
         let bob = client.bob_account();
-        let mint_msg = MockUSDCRef::mint(bob, 1000 * 10u128.pow(6));
+        let mint_msg = MockUSDCRef::mint(eth(bob), 1000 * 10u128.pow(6));
         client
             .call(&usdc_id, &mint_msg, 0)
             .await?;
 
         // 4. Bob approves tipping contract to spend USDC
         client.set_caller(bob);
-        let approve_msg = MockUSDCRef::approve(tipping_id, 1000 * 10u128.pow(6));
+        let approve_msg = MockUSDCRef::approve(eth(tipping_id), 1000 * 10u128.pow(6));
         client
             .call(&usdc_id, &approve_msg, 0)
             .await?;
@@ -61,7 +67,7 @@ mod integration_tests {
         assert_eq!(builder_info.total_received, 99 * 10u128.pow(6)); // 99 USDC after 1% fee
 
         // 7. Verify treasury received fee
-        let alice_balance = MockUSDCRef::balance_of(treasury);
+        let alice_balance = MockUSDCRef::balance_of(eth(treasury));
         let treasury_balance = client
             .call(&usdc_id, &alice_balance, 0)
             .await?
@@ -72,21 +78,33 @@ mod integration_tests {
         Ok(())
     }
 
-    /// Test cross-chain donation flow
+    /// Test cross-chain donation flow with USDC held in escrow
     #[ink_e2e::test]
     async fn test_cross_chain_donation(mut client: Client) -> E2EResult<()> {
-        // 1. Deploy cross-chain contract
-        let xcm_constructor = CrossChainDonationRef::new(1000, 1337);
+        // 1. Deploy USDC and the cross-chain contract
+        let usdc_constructor = MockUSDCRef::new(1_000_000 * 10u128.pow(6));
+        let usdc_id = client
+            .instantiate("psp22_usdc", &usdc_constructor, 0, None)
+            .await?
+            .account_id;
+
+        let reserve = client.charlie_account();
+        let xcm_constructor = CrossChainDonationRef::new(1000, 1337, usdc_id, reserve);
         let xcm_id = client
             .instantiate("cross_chain", &xcm_constructor, 0, None)
             .await?
             .account_id;
 
-        // 2. Alice donates to builder #2 (Bob on Astar)
+        // 2. Alice approves and donates to builder #2 (Bob on Astar)
         let alice = client.alice_account();
         client.set_caller(alice);
 
-        let donate_msg = CrossChainDonationRef::donate(2, 50 * 10u128.pow(6));
+        let approve_msg = MockUSDCRef::approve(eth(xcm_id), 50 * 10u128.pow(6));
+        client
+            .call(&usdc_id, &approve_msg, 0)
+            .await?;
+
+        let donate_msg = CrossChainDonationRef::donate(2, 50 * 10u128.pow(6), None);
         let result = client
             .call(&xcm_id, &donate_msg, 0)
             .await?;
@@ -106,7 +124,22 @@ mod integration_tests {
         assert_eq!(transfer.parachain_id, 2006); // Astar
         assert_eq!(transfer.status, TransferStatus::Sent);
 
-        // 4. Simulate confirmation (owner only)
+        // 4. The donation is held by the contract
+        let contract_balance = MockUSDCRef::balance_of(eth(xcm_id));
+        let held = client
+            .call(&usdc_id, &contract_balance, 0)
+            .await?
+            .return_value();
+        assert_eq!(held, 50 * 10u128.pow(6));
+
+        let get_escrowed = CrossChainDonationRef::get_escrowed(transfer_id);
+        let escrowed = client
+            .call(&xcm_id, &get_escrowed, 0)
+            .await?
+            .return_value();
+        assert_eq!(escrowed, 50 * 10u128.pow(6));
+
+        // 5. Simulate confirmation (owner only)
         let confirm_msg = CrossChainDonationRef::confirm_transfer(transfer_id);
         let confirm_result = client
             .call(&xcm_id, &confirm_msg, 0)
@@ -114,7 +147,7 @@ mod integration_tests {
 
         assert!(confirm_result.is_ok());
 
-        // 5. Verify transfer is confirmed
+        // 6. Verify transfer is confirmed and the escrow released
         let transfer_after = client
             .call(&xcm_id, &get_transfer, 0)
             .await?
@@ -123,6 +156,109 @@ mod integration_tests {
 
         assert_eq!(transfer_after.status, TransferStatus::Confirmed);
 
+        let get_total_escrowed = CrossChainDonationRef::get_total_escrowed();
+        let total_escrowed = client
+            .call(&xcm_id, &get_total_escrowed, 0)
+            .await?
+            .return_value();
+        assert_eq!(total_escrowed, 0);
+
+        // 7. The escrow went to the reserve that funded the XCM, not the owner
+        let reserve_balance = MockUSDCRef::balance_of(eth(reserve));
+        let paid = client
+            .call(&usdc_id, &reserve_balance, 0)
+            .await?
+            .return_value();
+        assert_eq!(paid, 50 * 10u128.pow(6));
+
+        let held = client
+            .call(&usdc_id, &contract_balance, 0)
+            .await?
+            .return_value();
+        assert_eq!(held, 0);
+
+        Ok(())
+    }
+
+    /// Test that a failed cross-chain donation is refunded from escrow
+    #[ink_e2e::test]
+    async fn test_cross_chain_refund(mut client: Client) -> E2EResult<()> {
+        let usdc_constructor = MockUSDCRef::new(1_000_000 * 10u128.pow(6));
+        let usdc_id = client
+            .instantiate("psp22_usdc", &usdc_constructor, 0, None)
+            .await?
+            .account_id;
+
+        let reserve = client.charlie_account();
+        let xcm_constructor = CrossChainDonationRef::new(1000, 1337, usdc_id, reserve);
+        let xcm_id = client
+            .instantiate("cross_chain", &xcm_constructor, 0, None)
+            .await?
+            .account_id;
+
+        // Mint USDC to Bob, who donates
+        let bob = client.bob_account();
+        let mint_msg = MockUSDCRef::mint(eth(bob), 100 * 10u128.pow(6));
+        client
+            .call(&usdc_id, &mint_msg, 0)
+            .await?;
+
+        client.set_caller(bob);
+        let approve_msg = MockUSDCRef::approve(eth(xcm_id), 100 * 10u128.pow(6));
+        client
+            .call(&usdc_id, &approve_msg, 0)
+            .await?;
+
+        let donate_msg = CrossChainDonationRef::donate(3, 40 * 10u128.pow(6), None);
+        let transfer_id = client
+            .call(&xcm_id, &donate_msg, 0)
+            .await?
+            .return_value()
+            .unwrap();
+
+        let bob_balance = MockUSDCRef::balance_of(eth(bob));
+        let balance = client
+            .call(&usdc_id, &bob_balance, 0)
+            .await?
+            .return_value();
+        assert_eq!(balance, 60 * 10u128.pow(6));
+
+        // Owner reports the failure, Bob claims the refund
+        let alice = client.alice_account();
+        client.set_caller(alice);
+        let report_msg = CrossChainDonationRef::report_failure(transfer_id, "FailedToTransactAsset".into());
+        client
+            .call(&xcm_id, &report_msg, 0)
+            .await?;
+
+        client.set_caller(bob);
+        let refund_msg = CrossChainDonationRef::refund(transfer_id);
+        let refund_result = client
+            .call(&xcm_id, &refund_msg, 0)
+            .await?;
+        assert!(refund_result.is_ok());
+
+        let balance = client
+            .call(&usdc_id, &bob_balance, 0)
+            .await?
+            .return_value();
+        assert_eq!(balance, 100 * 10u128.pow(6));
+
+        let contract_balance = MockUSDCRef::balance_of(eth(xcm_id));
+        let held = client
+            .call(&usdc_id, &contract_balance, 0)
+            .await?
+            .return_value();
+        assert_eq!(held, 0);
+
+        let get_transfer = CrossChainDonationRef::get_transfer(transfer_id);
+        let transfer = client
+            .call(&xcm_id, &get_transfer, 0)
+            .await?
+            .return_value()
+            .unwrap();
+        assert_eq!(transfer.status, TransferStatus::Refunded);
+
         Ok(())
     }
 
@@ -294,7 +430,9 @@ mod integration_tests {
     #[ink_e2e::test]
     async fn test_xcm_asset_support(mut client: Client) -> E2EResult<()> {
         // Deploy cross-chain contract
-        let xcm_constructor = CrossChainDonationRef::new(1000, 1337);
+        let usdc_id = AccountId::from([0x01; 32]);
+        let reserve = client.charlie_account();
+        let xcm_constructor = CrossChainDonationRef::new(1000, 1337, usdc_id, reserve);
         let xcm_id = client
             .instantiate("cross_chain", &xcm_constructor, 0, None)
             .await?
@@ -320,7 +458,15 @@ mod integration_tests {
         assert!(!random_supported);
 
         // Add new asset support
-        let add_asset = CrossChainDonationRef::add_supported_asset(42069); // USDP
+        let usdp = AssetConfig {
+            token: AccountId::from([0x02; 32]),
+            decimals: 18,
+            min_donation: 10u128.pow(18),
+            max_donation: 50_000 * 10u128.pow(18),
+            fee_asset_id: 1337,
+            enabled: true,
+        };
+        let add_asset = CrossChainDonationRef::add_supported_asset(42069, usdp); // USDP
         client
             .call(&xcm_id, &add_asset, 0)
             .await?;
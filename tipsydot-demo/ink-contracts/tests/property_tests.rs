@@ -5,6 +5,7 @@
 mod property_tests {
     use proptest::prelude::*;
     use ink::env::test;
+    use ink::primitives::H160;
 
     /// Generate random account IDs
     fn arbitrary_account_id() -> impl Strategy<Value = AccountId> {
@@ -12,6 +13,12 @@ mod property_tests {
             .prop_map(|bytes| AccountId::from(bytes))
     }
 
+    /// Generate random H160 addresses, as `MockUSDC` accounts are
+    fn arbitrary_address() -> impl Strategy<Value = H160> {
+        prop::array::uniform20(any::<u8>())
+            .prop_map(H160::from)
+    }
+
     /// Generate random balance amounts
     fn arbitrary_balance() -> impl Strategy<Value = Balance> {
         // Range from 0 to 1 billion USDC (with 6 decimals)
@@ -32,7 +39,7 @@ mod property_tests {
         #[test]
         fn psp22_total_supply_invariant(
             initial_supply in arbitrary_balance(),
-            transfers in prop::collection::vec((arbitrary_account_id(), arbitrary_account_id(), 1u128..=1000u128), 0..100)
+            transfers in prop::collection::vec((arbitrary_address(), arbitrary_address(), 1u128..=1000u128), 0..100)
         ) {
             let mut usdc = MockUSDC::new(initial_supply);
             let initial_total = usdc.total_supply();
@@ -42,7 +49,7 @@ mod property_tests {
                 // Ensure from has balance
                 let from_balance = usdc.balance_of(from);
                 if from_balance >= amount {
-                    test::set_caller::<Environment>(from);
+                    test::set_caller(from);
                     let _ = usdc.transfer(to, amount, vec![]);
                 }
             }
@@ -149,12 +156,12 @@ mod property_tests {
         fn xcm_transfer_amount_validation(
             amount in any::<u128>()
         ) {
-            let mut xcm = CrossChainDonation::new(1000, 1337);
+            let mut xcm = CrossChainDonation::new(1000, 1337, AccountId::from([0x01; 32]), AccountId::from([0x02; 32]));
 
-            let result = xcm.donate(1, amount);
+            let result = xcm.donate(1, amount, None);
 
-            if amount < 1_000_000 {
-                // Below minimum (1 USDC)
+            if amount < 1_000_000 || amount > 100_000_000_000 {
+                // Below minimum (1 USDC) or above maximum (100,000 USDC)
                 prop_assert_eq!(result, Err(CrossChainError::InvalidAmount));
             } else {
                 // Valid amount
@@ -170,11 +177,11 @@ mod property_tests {
                 1..50
             )
         ) {
-            let mut xcm = CrossChainDonation::new(1000, 1337);
+            let mut xcm = CrossChainDonation::new(1000, 1337, AccountId::from([0x01; 32]), AccountId::from([0x02; 32]));
             let mut transfer_ids = Vec::new();
 
             for (builder_id, amount) in donations {
-                if let Ok(id) = xcm.donate(builder_id, amount) {
+                if let Ok(id) = xcm.donate(builder_id, amount, None) {
                     // Verify ID is unique
                     prop_assert!(!transfer_ids.contains(&id));
 
@@ -219,8 +226,8 @@ mod property_tests {
         /// Test allowance mechanics
         #[test]
         fn psp22_allowance_safety(
-            owner in arbitrary_account_id(),
-            spender in arbitrary_account_id(),
+            owner in arbitrary_address(),
+            spender in arbitrary_address(),
             initial_allowance in arbitrary_balance(),
             transfers in prop::collection::vec(1u128..=1000u128, 0..10)
         ) {
@@ -230,18 +237,18 @@ mod property_tests {
             usdc.mint(owner, initial_allowance * 2).unwrap();
 
             // Set allowance
-            test::set_caller::<Environment>(owner);
+            test::set_caller(owner);
             usdc.approve(spender, initial_allowance).unwrap();
 
             let mut remaining_allowance = initial_allowance;
 
             // Spender attempts transfers
-            test::set_caller::<Environment>(spender);
+            test::set_caller(spender);
             for amount in transfers {
                 if amount <= remaining_allowance {
                     let result = usdc.transfer_from(
                         owner,
-                        AccountId::from([0x99; 32]),
+                        H160::from([0x99; 20]),
                         amount,
                         vec![]
                     );
@@ -265,9 +272,9 @@ mod property_tests {
             builder_id in any::<u32>(),
             amount in 1_000_000u128..=100_000_000u128
         ) {
-            let mut xcm = CrossChainDonation::new(1000, 1337);
+            let mut xcm = CrossChainDonation::new(1000, 1337, AccountId::from([0x01; 32]), AccountId::from([0x02; 32]));
 
-            let result = xcm.donate(builder_id, amount);
+            let result = xcm.donate(builder_id, amount, None);
 
             if builder_id >= 1 && builder_id <= 3 {
                 // Valid builder IDs (demo builders)
@@ -3,9 +3,11 @@
 #[ink::contract]
 pub mod tipping {
     use ink::prelude::{string::String, vec::Vec};
+    use ink::primitives::{H160, U256};
     use ink::storage::Mapping;
-    use openbrush::contracts::psp22::PSP22Ref;
+    use psp22_usdc::PSP22;
     use scale::{Decode, Encode};
+    use supporter_badge::{BadgeKind, Id};
 
     /// A parachain builder receiving tips
     #[derive(Debug, Clone, Encode, Decode)]
@@ -47,6 +49,35 @@ pub mod tipping {
         active: bool,
     }
 
+    /// A supporter badge could not be minted; the tip still went through
+    #[ink(event)]
+    pub struct BadgeMintFailed {
+        #[ink(topic)]
+        tipper: AccountId,
+        #[ink(topic)]
+        builder_id: u32,
+        kind: BadgeKind,
+        tier: u8,
+    }
+
+    /// Stray tokens recovered by the owner
+    #[ink(event)]
+    pub struct TokensRecovered {
+        #[ink(topic)]
+        token: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+        amount: Balance,
+    }
+
+    /// Stray native balance recovered by the owner
+    #[ink(event)]
+    pub struct NativeRecovered {
+        #[ink(topic)]
+        to: H160,
+        amount: U256,
+    }
+
     /// Errors that can occur in the contract
     #[derive(Debug, PartialEq, Eq, Encode, Decode)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
@@ -57,6 +88,9 @@ pub mod tipping {
         TransferFailed,
         Unauthorized,
         BuilderAlreadyExists,
+        InvalidBadgeTiers,
+        NotPaused,
+        PermitFailed,
     }
 
     /// The tipping contract storage
@@ -78,13 +112,19 @@ pub mod tipping {
         total_fees_collected: Balance,
         /// Contract pause state
         paused: bool,
+        /// Supporter badge contract (badges disabled when `None`)
+        badge_contract: Option<AccountId>,
+        /// Cumulative tip amounts per builder that earn a tier badge (ascending)
+        badge_tiers: Vec<Balance>,
+        /// Cumulative amount tipped per (tipper, builder)
+        supporter_totals: Mapping<(AccountId, u32), Balance>,
     }
 
     impl Tipping {
         /// Creates a new tipping contract
         #[ink(constructor)]
         pub fn new(usdc_token: AccountId, treasury: AccountId, protocol_fee_bps: u16) -> Self {
-            let caller = Self::env().caller();
+            let caller = AccountId::from(evm_interop::to_account(Self::env().caller()));
 
             // Initialize with demo builders
             let mut contract = Self {
@@ -96,6 +136,9 @@ pub mod tipping {
                 treasury,
                 total_fees_collected: 0,
                 paused: false,
+                badge_contract: None,
+                badge_tiers: Vec::new(),
+                supporter_totals: Mapping::new(),
             };
 
             // Add demo builders
@@ -206,34 +249,21 @@ pub mod tipping {
             amount: Balance,
             message: String,
         ) -> Result<(), TippingError> {
-            self.ensure_not_paused()?;
-
-            if amount == 0 {
-                return Err(TippingError::ZeroAmount);
-            }
+            let mut builder = self.ensure_can_tip(builder_id, amount)?;
 
-            let mut builder = self.builders.get(builder_id)
-                .ok_or(TippingError::InvalidBuilder)?;
-
-            if !builder.active {
-                return Err(TippingError::BuilderNotActive);
-            }
-
-            let caller = self.env().caller();
-            let usdc: PSP22Ref = self.usdc_token.into();
+            let caller = self.caller_account();
+            let tipper = self.env().caller();
 
             // Calculate fee
             let fee = (amount * self.protocol_fee_bps as u128) / 10_000;
             let tip_amount = amount - fee;
 
             // Transfer tip to builder
-            usdc.transfer_from(caller, builder.wallet, tip_amount, Vec::new())
-                .map_err(|_| TippingError::TransferFailed)?;
+            self.transfer_from(tipper, Self::eth_address(&builder.wallet), tip_amount)?;
 
             // Transfer fee to treasury if applicable
             if fee > 0 {
-                usdc.transfer_from(caller, self.treasury, fee, Vec::new())
-                    .map_err(|_| TippingError::TransferFailed)?;
+                self.transfer_from(tipper, Self::eth_address(&self.treasury), fee)?;
                 self.total_fees_collected += fee;
             }
 
@@ -241,6 +271,12 @@ pub mod tipping {
             builder.total_received += tip_amount;
             self.builders.insert(builder_id, &builder);
 
+            // Track supporter totals and award any badges earned
+            let previous_total = self.supporter_totals.get((caller, builder_id)).unwrap_or(0);
+            let new_total = previous_total + amount;
+            self.supporter_totals.insert((caller, builder_id), &new_total);
+            self.award_badges(caller, builder_id, previous_total, new_total);
+
             // Emit event
             self.env().emit_event(Tip {
                 tipper: caller,
@@ -253,6 +289,181 @@ pub mod tipping {
             Ok(())
         }
 
+        /// Approve and tip in one transaction using a signed USDC permit
+        ///
+        /// `signature` is the caller's EIP-2612 permit letting this contract
+        /// spend `amount` until `deadline`, so no separate `approve` is needed.
+        #[ink(message)]
+        pub fn tip_with_permit(
+            &mut self,
+            builder_id: u32,
+            amount: Balance,
+            message: String,
+            deadline: u64,
+            signature: [u8; 65],
+        ) -> Result<(), TippingError> {
+            self.ensure_can_tip(builder_id, amount)?;
+
+            let owner = self.env().caller();
+            self.permit(owner, self.env().address(), amount, deadline, signature)?;
+
+            self.tip(builder_id, amount, message)
+        }
+
+        /// Call `PSP22Permit::permit` on the USDC token
+        #[cfg(not(test))]
+        fn permit(
+            &self,
+            owner: H160,
+            spender: H160,
+            value: Balance,
+            deadline: u64,
+            signature: [u8; 65],
+        ) -> Result<(), TippingError> {
+            use ink::env::call::{build_call, ExecutionInput, Selector};
+
+            build_call::<Environment>()
+                .call(Self::eth_address(&self.usdc_token))
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP22Permit::permit")))
+                        .push_arg(owner)
+                        .push_arg(spender)
+                        .push_arg(value)
+                        .push_arg(deadline)
+                        .push_arg(signature)
+                )
+                .returns::<Result<(), psp22_usdc::PSP22Error>>()
+                .try_invoke()
+                .map_err(|_| TippingError::PermitFailed)?
+                .map_err(|_| TippingError::PermitFailed)?
+                .map_err(|_| TippingError::PermitFailed)
+        }
+
+        #[cfg(test)]
+        fn permit(
+            &self,
+            owner: H160,
+            spender: H160,
+            value: Balance,
+            deadline: u64,
+            signature: [u8; 65],
+        ) -> Result<(), TippingError> {
+            tests::permit(owner, spender, value, deadline, signature)
+        }
+
+        /// Move USDC from `from` to `to` out of this contract's allowance
+        #[cfg(not(test))]
+        fn transfer_from(&self, from: H160, to: H160, value: Balance) -> Result<(), TippingError> {
+            let mut usdc: ink::contract_ref!(PSP22) = Self::eth_address(&self.usdc_token).into();
+            usdc.transfer_from(from, to, value, Vec::new())
+                .map_err(|_| TippingError::TransferFailed)
+        }
+
+        #[cfg(test)]
+        fn transfer_from(&self, from: H160, to: H160, value: Balance) -> Result<(), TippingError> {
+            tests::transfer_from(self.env().address(), from, to, value)
+                .map_err(|_| TippingError::TransferFailed)
+        }
+
+        /// Account the caller's H160 maps to, which keys builders and totals
+        fn caller_account(&self) -> AccountId {
+            AccountId::from(evm_interop::to_account(self.env().caller()))
+        }
+
+        /// Address the H160 token sees for `account` (pallet-revive's account mapping)
+        fn eth_address(account: &AccountId) -> H160 {
+            evm_interop::to_address(account.as_ref())
+        }
+
+        /// Configure supporter badges (only owner)
+        ///
+        /// `tiers` are cumulative amounts tipped to a single builder, in
+        /// strictly ascending order; crossing the n-th one earns tier n.
+        #[ink(message)]
+        pub fn set_badge_config(
+            &mut self,
+            badge_contract: Option<AccountId>,
+            tiers: Vec<Balance>,
+        ) -> Result<(), TippingError> {
+            self.ensure_owner()?;
+
+            if tiers.len() > u8::MAX as usize
+                || tiers.first() == Some(&0)
+                || tiers.windows(2).any(|pair| pair[0] >= pair[1])
+            {
+                return Err(TippingError::InvalidBadgeTiers);
+            }
+
+            self.badge_contract = badge_contract;
+            self.badge_tiers = tiers;
+            Ok(())
+        }
+
+        /// Get badge contract and tier thresholds
+        #[ink(message)]
+        pub fn get_badge_config(&self) -> (Option<AccountId>, Vec<Balance>) {
+            (self.badge_contract, self.badge_tiers.clone())
+        }
+
+        /// Get the cumulative amount a tipper has sent to a builder
+        #[ink(message)]
+        pub fn get_supporter_total(&self, tipper: AccountId, builder_id: u32) -> Balance {
+            self.supporter_totals.get((tipper, builder_id)).unwrap_or(0)
+        }
+
+        /// Mint the badges earned by moving from `previous_total` to `new_total`
+        fn award_badges(&self, tipper: AccountId, builder_id: u32, previous_total: Balance, new_total: Balance) {
+            let Some(badge_contract) = self.badge_contract else {
+                return;
+            };
+
+            if previous_total == 0 {
+                self.mint_badge(badge_contract, tipper, BadgeKind::FirstTip, builder_id, 0);
+            }
+
+            for tier in Self::crossed_tiers(&self.badge_tiers, previous_total, new_total) {
+                self.mint_badge(badge_contract, tipper, BadgeKind::AmountTier, builder_id, tier);
+            }
+        }
+
+        /// 1-based tiers whose threshold lies in `(previous_total, new_total]`
+        fn crossed_tiers(tiers: &[Balance], previous_total: Balance, new_total: Balance) -> Vec<u8> {
+            tiers
+                .iter()
+                .enumerate()
+                .filter(|(_, threshold)| previous_total < **threshold && **threshold <= new_total)
+                .map(|(index, _)| (index + 1) as u8)
+                .collect()
+        }
+
+        /// Mint a badge on the badge contract
+        ///
+        /// The badge is held by the tipper's H160, so it shows up under the
+        /// same address their USDC does. Minting is best-effort: a
+        /// misconfigured or failing badge contract must never block a tip, so
+        /// a badge that isn't minted is reported with `BadgeMintFailed`.
+        fn mint_badge(&self, badge_contract: AccountId, to: AccountId, kind: BadgeKind, builder_id: u32, tier: u8) {
+            let minted = self.try_mint_badge(Self::eth_address(&badge_contract), Self::eth_address(&to), kind, builder_id, tier);
+            if minted.is_none() {
+                self.env().emit_event(BadgeMintFailed {
+                    tipper: to,
+                    builder_id,
+                    kind,
+                    tier,
+                });
+            }
+        }
+
+        #[cfg(not(test))]
+        fn try_mint_badge(&self, badge_contract: H160, to: H160, kind: BadgeKind, builder_id: u32, tier: u8) -> Option<Id> {
+            supporter_badge::try_mint_badge(badge_contract, to, kind, builder_id, None, tier)
+        }
+
+        #[cfg(test)]
+        fn try_mint_badge(&self, badge_contract: H160, to: H160, kind: BadgeKind, builder_id: u32, tier: u8) -> Option<Id> {
+            tests::mint_badge(badge_contract, to, kind, builder_id, tier)
+        }
+
         /// Get builder details
         #[ink(message)]
         pub fn get_builder(&self, builder_id: u32) -> Option<Builder> {
@@ -318,9 +529,44 @@ pub mod tipping {
             Ok(())
         }
 
+        /// Recover PSP22 tokens sent to the contract by mistake (only owner, while paused)
+        ///
+        /// Tips are forwarded as they are made, so the contract never holds
+        /// funds of its own.
+        #[ink(message)]
+        pub fn recover_tokens(
+            &mut self,
+            token: AccountId,
+            to: AccountId,
+            amount: Balance,
+        ) -> Result<(), TippingError> {
+            self.ensure_owner()?;
+            self.ensure_paused()?;
+
+            let mut psp22: ink::contract_ref!(PSP22) = Self::eth_address(&token).into();
+            psp22.transfer(Self::eth_address(&to), amount, Vec::new())
+                .map_err(|_| TippingError::TransferFailed)?;
+
+            self.env().emit_event(TokensRecovered { token, to, amount });
+            Ok(())
+        }
+
+        /// Recover native balance sent to the contract by mistake (only owner, while paused)
+        #[ink(message)]
+        pub fn recover_native(&mut self, to: H160, amount: U256) -> Result<(), TippingError> {
+            self.ensure_owner()?;
+            self.ensure_paused()?;
+
+            self.env().transfer(to, amount)
+                .map_err(|_| TippingError::TransferFailed)?;
+
+            self.env().emit_event(NativeRecovered { to, amount });
+            Ok(())
+        }
+
         /// Helper: ensure caller is owner
         fn ensure_owner(&self) -> Result<(), TippingError> {
-            if self.env().caller() != self.owner {
+            if self.caller_account() != self.owner {
                 return Err(TippingError::Unauthorized);
             }
             Ok(())
@@ -333,19 +579,146 @@ pub mod tipping {
             }
             Ok(())
         }
+
+        /// Helper: check a tip can go ahead, returning the builder
+        fn ensure_can_tip(&self, builder_id: u32, amount: Balance) -> Result<Builder, TippingError> {
+            self.ensure_not_paused()?;
+
+            if amount == 0 {
+                return Err(TippingError::ZeroAmount);
+            }
+
+            let builder = self.builders.get(builder_id)
+                .ok_or(TippingError::InvalidBuilder)?;
+
+            if !builder.active {
+                return Err(TippingError::BuilderNotActive);
+            }
+            Ok(builder)
+        }
+
+        /// Helper: ensure contract is paused
+        fn ensure_paused(&self) -> Result<(), TippingError> {
+            if !self.paused {
+                return Err(TippingError::NotPaused);
+            }
+            Ok(())
+        }
     }
 
     #[cfg(test)]
     mod tests {
         use super::*;
         use ink::env::test;
+        use psp22_usdc::PSP22Error;
+        use std::cell::RefCell;
+        use std::collections::HashMap;
+
+        /// Stand-in for the USDC token: balances, allowances and the permits
+        /// it accepted. Pause and blocklist checks are covered end to end
+        /// against `MockUSDC` itself.
+        #[derive(Default)]
+        pub struct MockUsdc {
+            pub balances: HashMap<H160, Balance>,
+            pub allowances: HashMap<(H160, H160), Balance>,
+            pub permits: Vec<(H160, H160, Balance, u64, [u8; 65])>,
+        }
+
+        /// Stand-in for the badge contract: the badges it minted and whether
+        /// it refuses to mint
+        #[derive(Default)]
+        pub struct MockBadges {
+            pub minted: Vec<(H160, BadgeKind, u32, u8)>,
+            pub refusing: bool,
+        }
+
+        thread_local! {
+            pub static MOCK_USDC: RefCell<MockUsdc> = RefCell::new(MockUsdc::default());
+            pub static MOCK_BADGES: RefCell<MockBadges> = RefCell::new(MockBadges::default());
+        }
 
-        fn default_accounts() -> test::DefaultAccounts<Environment> {
-            test::default_accounts::<Environment>()
+        pub fn mint_badge(_badge_contract: H160, to: H160, kind: BadgeKind, builder_id: u32, tier: u8) -> Option<Id> {
+            MOCK_BADGES.with(|mock| {
+                let mut mock = mock.borrow_mut();
+                if mock.refusing {
+                    return None;
+                }
+                mock.minted.push((to, kind, builder_id, tier));
+                Some(Id::U128(mock.minted.len() as u128))
+            })
+        }
+
+        pub fn permit(
+            owner: H160,
+            spender: H160,
+            value: Balance,
+            deadline: u64,
+            signature: [u8; 65],
+        ) -> Result<(), TippingError> {
+            MOCK_USDC.with(|mock| {
+                let mut mock = mock.borrow_mut();
+                mock.allowances.insert((owner, spender), value);
+                mock.permits.push((owner, spender, value, deadline, signature));
+            });
+            Ok(())
+        }
+
+        /// `MockUSDC::transfer_from`'s allowance and balance checks
+        pub fn transfer_from(spender: H160, from: H160, to: H160, value: Balance) -> Result<(), PSP22Error> {
+            MOCK_USDC.with(|mock| {
+                let mut mock = mock.borrow_mut();
+                let allowance = mock.allowances.get(&(from, spender)).copied().unwrap_or_default();
+                if allowance < value {
+                    return Err(PSP22Error::InsufficientAllowance);
+                }
+                let balance = balance_of(&mock, from);
+                if balance < value {
+                    return Err(PSP22Error::InsufficientBalance);
+                }
+                mock.allowances.insert((from, spender), allowance - value);
+                mock.balances.insert(from, balance - value);
+                let to_balance = balance_of(&mock, to);
+                mock.balances.insert(to, to_balance + value);
+                Ok(())
+            })
+        }
+
+        fn balance_of(mock: &MockUsdc, holder: H160) -> Balance {
+            mock.balances.get(&holder).copied().unwrap_or_default()
+        }
+
+        fn usdc_balance(holder: &AccountId) -> Balance {
+            MOCK_USDC.with(|mock| balance_of(&mock.borrow(), Tipping::eth_address(holder)))
+        }
+
+        /// The default test accounts, as the accounts their H160s map to
+        struct Accounts {
+            alice: AccountId,
+            bob: AccountId,
+            charlie: AccountId,
+            django: AccountId,
+            eve: AccountId,
+        }
+
+        fn default_accounts() -> Accounts {
+            let accounts = test::default_accounts();
+            let account = |address: H160| AccountId::from(evm_interop::to_account(address));
+            Accounts {
+                alice: account(accounts.alice),
+                bob: account(accounts.bob),
+                charlie: account(accounts.charlie),
+                django: account(accounts.django),
+                eve: account(accounts.eve),
+            }
+        }
+
+        fn set_caller(account: AccountId) {
+            test::set_caller(Tipping::eth_address(&account));
         }
 
         fn create_contract() -> Tipping {
             let accounts = default_accounts();
+            set_caller(accounts.alice);
             Tipping::new(accounts.charlie, accounts.django, 100) // 1% fee
         }
 
@@ -382,7 +755,7 @@ pub mod tipping {
             let mut contract = create_contract();
             let accounts = default_accounts();
 
-            test::set_caller::<Environment>(accounts.bob);
+            set_caller(accounts.bob);
             let result = contract.add_builder(
                 "New Builder".into(),
                 "Description".into(),
@@ -445,10 +818,125 @@ pub mod tipping {
             let mut contract = create_contract();
             let accounts = default_accounts();
 
-            test::set_caller::<Environment>(accounts.bob);
+            set_caller(accounts.bob);
             assert_eq!(contract.pause(), Err(TippingError::Unauthorized));
         }
 
+        #[ink::test]
+        fn recover_native_works() {
+            let mut contract = create_contract();
+            let accounts = default_accounts();
+            let eve = test::default_accounts().eve;
+            let amount = U256::from(1_000_000);
+            test::set_account_balance(test::callee(), U256::from(5_000_000));
+            let before = test::get_account_balance::<Environment>(eve).unwrap();
+
+            // Only while paused
+            assert_eq!(contract.recover_native(eve, amount), Err(TippingError::NotPaused));
+            contract.pause().unwrap();
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.recover_native(eve, amount), Err(TippingError::Unauthorized));
+            assert_eq!(
+                contract.recover_tokens(accounts.charlie, accounts.eve, 1_000_000),
+                Err(TippingError::Unauthorized)
+            );
+
+            set_caller(accounts.alice);
+            assert!(contract.recover_native(eve, amount).is_ok());
+            assert_eq!(test::get_account_balance::<Environment>(eve).unwrap(), before + amount);
+            assert_eq!(test::recorded_events().count(), 4); // 3 demo builders + recovery
+        }
+
+        #[ink::test]
+        fn tip_with_permit_validates_before_permit() {
+            let mut contract = create_contract();
+            let signature = [0u8; 65];
+
+            assert_eq!(
+                contract.tip_with_permit(0, 0, String::new(), 0, signature),
+                Err(TippingError::ZeroAmount)
+            );
+            assert_eq!(
+                contract.tip_with_permit(99, 10, String::new(), 0, signature),
+                Err(TippingError::InvalidBuilder)
+            );
+
+            contract.pause().unwrap();
+            assert_eq!(
+                contract.tip_with_permit(1, 10, String::new(), 0, signature),
+                Err(TippingError::Unauthorized)
+            );
+        }
+
+        #[ink::test]
+        fn tip_with_permit_works() {
+            let mut contract = create_contract();
+            let accounts = default_accounts();
+            let tipper = Tipping::eth_address(&accounts.bob);
+            let wallet = contract.get_builder(1).unwrap().wallet;
+            let signature = [0x5A; 65];
+            MOCK_USDC.with(|mock| mock.borrow_mut().balances.insert(tipper, 100 * 10u128.pow(6)));
+
+            set_caller(accounts.bob);
+            assert!(contract
+                .tip_with_permit(1, 10 * 10u128.pow(6), "No approve needed".into(), 5_000, signature)
+                .is_ok());
+
+            // The permit lets this contract spend exactly the tip...
+            let spender = ink::env::address();
+            let permits = MOCK_USDC.with(|mock| mock.borrow().permits.clone());
+            assert_eq!(permits, vec![(tipper, spender, 10 * 10u128.pow(6), 5_000, signature)]);
+            assert_eq!(
+                MOCK_USDC.with(|mock| mock.borrow().allowances.get(&(tipper, spender)).copied()),
+                Some(0)
+            );
+
+            // ...which is split between the builder and the treasury
+            assert_eq!(usdc_balance(&wallet), 9_900_000);
+            assert_eq!(usdc_balance(&accounts.django), 100_000);
+            assert_eq!(usdc_balance(&accounts.bob), 90 * 10u128.pow(6));
+            assert_eq!(contract.get_builder(1).unwrap().total_received, 9_900_000);
+            assert_eq!(contract.get_supporter_total(accounts.bob, 1), 10 * 10u128.pow(6));
+        }
+
+        #[ink::test]
+        fn tip_fails_when_usdc_rejects_transfer() {
+            let mut contract = create_contract();
+            let accounts = default_accounts();
+            let tipper = Tipping::eth_address(&accounts.bob);
+            MOCK_USDC.with(|mock| {
+                let mut mock = mock.borrow_mut();
+                mock.balances.insert(tipper, 1000 * 10u128.pow(6));
+                mock.allowances.insert((tipper, ink::env::address()), 5 * 10u128.pow(6));
+            });
+            set_caller(accounts.bob);
+
+            // The token's error surfaces as a failed transfer
+            assert_eq!(
+                contract.tip(1, 10 * 10u128.pow(6), "Still there?".into()),
+                Err(TippingError::TransferFailed)
+            );
+
+            // Nothing moved and no stats were recorded
+            assert_eq!(usdc_balance(&accounts.bob), 1000 * 10u128.pow(6));
+            assert_eq!(contract.get_builder(1).unwrap().total_received, 0);
+            assert_eq!(contract.get_supporter_total(accounts.bob, 1), 0);
+        }
+
+        #[ink::test]
+        fn eth_address_follows_revive_mapping() {
+            // Ethereum-derived accounts map back to their address
+            let mut bytes = [0xEE; 32];
+            bytes[..20].copy_from_slice(&[0x11; 20]);
+            assert_eq!(Tipping::eth_address(&AccountId::from(bytes)), H160::from([0x11; 20]));
+
+            // Native accounts map to the tail of their Keccak-256 hash
+            let native = Tipping::eth_address(&AccountId::from([0x01; 32]));
+            assert_ne!(native, H160::from([0x01; 20]));
+            assert_eq!(native, Tipping::eth_address(&AccountId::from([0x01; 32])));
+        }
+
         #[ink::test]
         fn update_fee_works() {
             let mut contract = create_contract();
@@ -457,5 +945,352 @@ pub mod tipping {
             let (_, _, fee) = contract.get_stats();
             assert_eq!(fee, 250);
         }
+
+        #[ink::test]
+        fn set_badge_config_works() {
+            let mut contract = create_contract();
+            let accounts = default_accounts();
+
+            let tiers = vec![10 * 10u128.pow(6), 100 * 10u128.pow(6)];
+            assert!(contract.set_badge_config(Some(accounts.eve), tiers.clone()).is_ok());
+            assert_eq!(contract.get_badge_config(), (Some(accounts.eve), tiers));
+
+            // Tiers must be non-zero and strictly ascending
+            assert_eq!(
+                contract.set_badge_config(Some(accounts.eve), vec![100, 10]),
+                Err(TippingError::InvalidBadgeTiers)
+            );
+            assert_eq!(
+                contract.set_badge_config(Some(accounts.eve), vec![0, 10]),
+                Err(TippingError::InvalidBadgeTiers)
+            );
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                contract.set_badge_config(None, Vec::new()),
+                Err(TippingError::Unauthorized)
+            );
+        }
+
+        #[ink::test]
+        fn crossed_tiers_works() {
+            let tiers = [10, 100, 1000];
+
+            assert_eq!(Tipping::crossed_tiers(&tiers, 0, 5), Vec::<u8>::new());
+            assert_eq!(Tipping::crossed_tiers(&tiers, 0, 10), vec![1]);
+            assert_eq!(Tipping::crossed_tiers(&tiers, 10, 99), Vec::<u8>::new());
+            assert_eq!(Tipping::crossed_tiers(&tiers, 50, 5000), vec![2, 3]);
+        }
+
+        #[ink::test]
+        fn failed_badge_mints_are_reported() {
+            let mut contract = create_contract();
+            let accounts = default_accounts();
+            let tipper = Tipping::eth_address(&accounts.bob);
+            MOCK_USDC.with(|mock| {
+                let mut mock = mock.borrow_mut();
+                mock.balances.insert(tipper, 100 * 10u128.pow(6));
+                mock.allowances.insert((tipper, ink::env::address()), 100 * 10u128.pow(6));
+            });
+            contract.set_badge_config(Some(accounts.eve), vec![5 * 10u128.pow(6), 15 * 10u128.pow(6)]).unwrap();
+            set_caller(accounts.bob);
+
+            // The first tip earns the first-tip and tier 1 badges
+            contract.tip(1, 10 * 10u128.pow(6), "First".into()).unwrap();
+            MOCK_BADGES.with(|mock| {
+                assert_eq!(mock.borrow().minted, vec![
+                    (tipper, BadgeKind::FirstTip, 1, 0),
+                    (tipper, BadgeKind::AmountTier, 1, 1),
+                ]);
+            });
+
+            // A refused mint doesn't block the tip, but is reported
+            MOCK_BADGES.with(|mock| mock.borrow_mut().refusing = true);
+            let emitted_before = test::recorded_events().count();
+            contract.tip(1, 10 * 10u128.pow(6), "Second".into()).unwrap();
+            assert_eq!(contract.get_supporter_total(accounts.bob, 1), 20 * 10u128.pow(6));
+
+            // BadgeMintFailed, then Tip
+            let events = test::recorded_events().skip(emitted_before).collect::<Vec<_>>();
+            assert_eq!(events.len(), 2);
+            let failed = BadgeMintFailed::decode(&mut &events[0].data[..]).unwrap();
+            assert_eq!(failed.tipper, accounts.bob);
+            assert_eq!(failed.builder_id, 1);
+            assert_eq!(failed.kind, BadgeKind::AmountTier);
+            assert_eq!(failed.tier, 2);
+            assert_eq!(MOCK_BADGES.with(|mock| mock.borrow().minted.len()), 2);
+        }
+    }
+
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        use super::*;
+        use ink::env::hash::{HashOutput, Keccak256};
+        use ink_e2e::subxt::dynamic::{self, Value};
+        use ink_e2e::subxt::tx::DynamicPayload;
+        use ink_e2e::subxt::{OnlineClient, PolkadotConfig};
+        use ink_e2e::{ContractsBackend, Keypair};
+        use psp22_usdc::psp22_usdc::{MockUSDC, MockUSDCRef};
+        use psp22_usdc::{PSP22, PSP22Permit};
+
+        type E2EResult<T> = Result<T, Box<dyn std::error::Error>>;
+        type Node = OnlineClient<PolkadotConfig>;
+
+        /// Secret key of the Ethereum wallet that tips
+        const TIPPER_SECRET: [u8; 32] = [0x42; 32];
+
+        fn keccak(input: &[u8]) -> [u8; 32] {
+            let mut hash = <Keccak256 as HashOutput>::Type::default();
+            ink::env::hash_bytes::<Keccak256>(input, &mut hash);
+            hash
+        }
+
+        /// H160 pallet-revive maps a keypair's account to
+        fn address(keypair: &Keypair) -> H160 {
+            H160::from_slice(&keccak(&keypair.public_key().0)[12..])
+        }
+
+        /// Left-pad big-endian bytes to an ABI word
+        fn word(bytes: &[u8]) -> [u8; 32] {
+            let mut word = [0u8; 32];
+            word[32 - bytes.len()..].copy_from_slice(bytes);
+            word
+        }
+
+        /// Ethereum address of a secp256k1 secret key
+        fn eth_wallet(secret: &[u8; 32]) -> H160 {
+            let secp = secp256k1::Secp256k1::new();
+            let key = secp256k1::SecretKey::from_slice(secret).unwrap();
+            let public_key = secp256k1::PublicKey::from_secret_key(&secp, &key).serialize_uncompressed();
+            H160::from_slice(&keccak(&public_key[1..])[12..])
+        }
+
+        /// EIP-712 digest of a permit, as `MockUSDC` verifies it
+        fn permit_digest(
+            domain_separator: [u8; 32],
+            owner: H160,
+            spender: H160,
+            value: Balance,
+            nonce: u64,
+            deadline: u64,
+        ) -> [u8; 32] {
+            let struct_hash = keccak(
+                &[
+                    keccak(b"Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)"),
+                    word(owner.as_bytes()),
+                    word(spender.as_bytes()),
+                    word(&value.to_be_bytes()),
+                    word(&nonce.to_be_bytes()),
+                    word(&deadline.to_be_bytes()),
+                ]
+                .concat(),
+            );
+            keccak(&[&b"\x19\x01"[..], &domain_separator, &struct_hash].concat())
+        }
+
+        /// Sign `digest` as a wallet would, with a 0/1 recovery id
+        fn sign(secret: &[u8; 32], digest: [u8; 32]) -> [u8; 65] {
+            let secp = secp256k1::Secp256k1::new();
+            let key = secp256k1::SecretKey::from_slice(secret).unwrap();
+            let message = secp256k1::Message::from_digest_slice(&digest).unwrap();
+            let (recovery_id, compact) = secp.sign_ecdsa_recoverable(&message, &key).serialize_compact();
+
+            let mut signature = [0u8; 65];
+            signature[..64].copy_from_slice(&compact);
+            signature[64] = recovery_id.to_i32() as u8;
+            signature
+        }
+
+        async fn node() -> E2EResult<Node> {
+            let url = std::env::var("CONTRACTS_NODE_URL").unwrap_or_else(|_| "ws://127.0.0.1:9944".into());
+            Ok(Node::from_url(url).await?)
+        }
+
+        /// Submit an extrinsic signed by Alice and wait for it to succeed
+        async fn submit(node: &Node, tx: DynamicPayload) -> E2EResult<()> {
+            node.tx()
+                .sign_and_submit_then_watch_default(&tx, &ink_e2e::alice())
+                .await?
+                .wait_for_finalized_success()
+                .await?;
+            Ok(())
+        }
+
+        /// Call `contract` from an Ethereum wallet.
+        ///
+        /// A keypair's account maps to a hashed address no secp256k1 key
+        /// signs for, so Alice (sudo on the dev node) dispatches the call
+        /// from the wallet's Ethereum-derived account, which pallet-revive
+        /// maps back to the wallet's own address.
+        async fn call_as_wallet(node: &Node, wallet: H160, contract: H160, data: Vec<u8>) -> E2EResult<()> {
+            let call = Value::unnamed_variant(
+                "Revive",
+                [Value::named_variant(
+                    "call",
+                    [
+                        ("dest", Value::from_bytes(contract.as_bytes())),
+                        ("value", Value::u128(0)),
+                        (
+                            "gas_limit",
+                            Value::named_composite([
+                                ("ref_time", Value::u128(500_000_000_000)),
+                                ("proof_size", Value::u128(5_000_000)),
+                            ]),
+                        ),
+                        ("storage_deposit_limit", Value::u128(u128::MAX)),
+                        ("data", Value::from_bytes(data)),
+                    ],
+                )],
+            );
+            let who = Value::unnamed_variant("Id", [Value::from_bytes(evm_interop::to_account(wallet))]);
+            submit(node, dynamic::tx("Sudo", "sudo_as", vec![who, call])).await
+        }
+
+        #[ink_e2e::test]
+        async fn e2e_tip_with_signed_permit<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+            let node = node().await?;
+            let tipper = eth_wallet(&TIPPER_SECRET);
+            let tipper_account = AccountId::from(evm_interop::to_account(tipper));
+
+            // The wallet holds 100 USDC, plus native balance for deposits
+            let mut constructor = MockUSDCRef::new(1_000 * 10u128.pow(6));
+            let usdc = client
+                .instantiate("psp22_usdc", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await?;
+            let mut usdc_calls = usdc.call_builder::<MockUSDC>();
+            let transfer = usdc_calls.transfer(tipper, 100 * 10u128.pow(6), vec![]);
+            let transfer_result = client.call(&ink_e2e::alice(), &transfer).submit().await?;
+            assert!(transfer_result.return_value().is_ok());
+
+            let dest = Value::unnamed_variant("Id", [Value::from_bytes(tipper_account)]);
+            let fund = dynamic::tx("Balances", "transfer_keep_alive", vec![dest, Value::u128(10u128.pow(15))]);
+            submit(&node, fund).await?;
+
+            let treasury = AccountId::from(evm_interop::to_account(H160::from([0x7E; 20])));
+            let mut constructor = TippingRef::new(AccountId::from(evm_interop::to_account(usdc.addr)), treasury, 100);
+            let tipping = client
+                .instantiate("tipping", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await?;
+            let tipping_calls = tipping.call_builder::<Tipping>();
+
+            // The wallet signs a permit for the tip instead of approving
+            let amount = 10 * 10u128.pow(6);
+            let deadline = u64::MAX;
+            let domain_separator = client
+                .call(&ink_e2e::alice(), &usdc_calls.domain_separator())
+                .dry_run()
+                .await?
+                .return_value();
+            let digest = permit_digest(domain_separator, tipper, tipping.addr, amount, 0, deadline);
+            let signature = sign(&TIPPER_SECRET, digest);
+
+            let mut data = ink::selector_bytes!("tip_with_permit").to_vec();
+            (1u32, amount, String::from("Signed, never approved"), deadline, signature).encode_to(&mut data);
+            call_as_wallet(&node, tipper, tipping.addr, data).await?;
+
+            // The permit was used up...
+            let nonce = client.call(&ink_e2e::alice(), &usdc_calls.nonces(tipper)).dry_run().await?.return_value();
+            assert_eq!(nonce, 1);
+            let allowance = usdc_calls.allowance(tipper, tipping.addr);
+            let allowance = client.call(&ink_e2e::alice(), &allowance).dry_run().await?.return_value();
+            assert_eq!(allowance, 0);
+
+            // ...and the tip split between the builder and the treasury
+            for (holder, expected) in [
+                (tipper, 90 * 10u128.pow(6)),
+                (evm_interop::to_address(&[0x01; 32]), 9_900_000),
+                (H160::from([0x7E; 20]), 100_000),
+            ] {
+                let balance_of = usdc_calls.balance_of(holder);
+                let balance = client.call(&ink_e2e::alice(), &balance_of).dry_run().await?.return_value();
+                assert_eq!(balance, expected);
+            }
+
+            let builder = client
+                .call(&ink_e2e::alice(), &tipping_calls.get_builder(1))
+                .dry_run()
+                .await?
+                .return_value()
+                .unwrap();
+            assert_eq!(builder.total_received, 9_900_000);
+            let supporter_total = tipping_calls.get_supporter_total(tipper_account, 1);
+            let total = client.call(&ink_e2e::alice(), &supporter_total).dry_run().await?.return_value();
+            assert_eq!(total, amount);
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn e2e_tip_fails_on_usdc_compliance<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+            let tipper = address(&ink_e2e::bob());
+            let wallet = evm_interop::to_address(&[0x01; 32]);
+
+            // Bob holds 100 USDC and lets the tipping contract spend it
+            let mut constructor = MockUSDCRef::new(1_000 * 10u128.pow(6));
+            let usdc = client
+                .instantiate("psp22_usdc", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await?;
+            let mut usdc_calls = usdc.call_builder::<MockUSDC>();
+            let transfer = usdc_calls.transfer(tipper, 100 * 10u128.pow(6), vec![]);
+            let transfer_result = client.call(&ink_e2e::alice(), &transfer).submit().await?;
+            assert!(transfer_result.return_value().is_ok());
+
+            let treasury = AccountId::from(evm_interop::to_account(H160::from([0x7E; 20])));
+            let mut constructor = TippingRef::new(AccountId::from(evm_interop::to_account(usdc.addr)), treasury, 100);
+            let tipping = client
+                .instantiate("tipping", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await?;
+            let mut tipping_calls = tipping.call_builder::<Tipping>();
+
+            let approve = usdc_calls.approve(tipping.addr, 100 * 10u128.pow(6));
+            let approve_result = client.call(&ink_e2e::bob(), &approve).submit().await?;
+            assert!(approve_result.return_value().is_ok());
+
+            let amount = 10 * 10u128.pow(6);
+            let tip = tipping_calls.tip(1, amount, "Still there?".into());
+
+            // Paused token
+            let pause_result = client.call(&ink_e2e::alice(), &usdc_calls.pause()).submit().await?;
+            assert!(pause_result.return_value().is_ok());
+            let tip_result = client.call(&ink_e2e::bob(), &tip).dry_run().await?;
+            assert_eq!(tip_result.return_value(), Err(TippingError::TransferFailed));
+            let unpause_result = client.call(&ink_e2e::alice(), &usdc_calls.unpause()).submit().await?;
+            assert!(unpause_result.return_value().is_ok());
+
+            // Blocklisted tipper, then blocklisted builder wallet
+            for account in [tipper, wallet] {
+                let blocklist = usdc_calls.blocklist(account);
+                let blocklist_result = client.call(&ink_e2e::alice(), &blocklist).submit().await?;
+                assert!(blocklist_result.return_value().is_ok());
+
+                let tip_result = client.call(&ink_e2e::bob(), &tip).dry_run().await?;
+                assert_eq!(tip_result.return_value(), Err(TippingError::TransferFailed));
+
+                let unblocklist = usdc_calls.unblocklist(account);
+                let unblocklist_result = client.call(&ink_e2e::alice(), &unblocklist).submit().await?;
+                assert!(unblocklist_result.return_value().is_ok());
+            }
+
+            // The same tip goes through once the token lets it
+            let tip_result = client.call(&ink_e2e::bob(), &tip).submit().await?;
+            assert_eq!(tip_result.return_value(), Ok(()));
+
+            let balance_of = usdc_calls.balance_of(tipper);
+            let balance = client.call(&ink_e2e::alice(), &balance_of).dry_run().await?.return_value();
+            assert_eq!(balance, 90 * 10u128.pow(6));
+            let builder = client
+                .call(&ink_e2e::alice(), &tipping_calls.get_builder(1))
+                .dry_run()
+                .await?
+                .return_value()
+                .unwrap();
+            assert_eq!(builder.total_received, 9_900_000);
+
+            Ok(())
+        }
     }
-}
\ No newline at end of file
+}
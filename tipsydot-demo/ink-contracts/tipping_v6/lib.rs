@@ -1,8 +1,7 @@
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
 use ink::prelude::vec::Vec;
-use ink::primitives::{AccountId as InkAccountId, Balance};
-use ink_primitives::types::{H160, U256};
+use ink::primitives::{H160, U256};
 
 /// TipsyDot Tipping Contract v6 with full EVM compatibility
 /// 
@@ -14,8 +13,9 @@ use ink_primitives::types::{H160, U256};
 #[ink::contract]
 mod tipping_v6 {
     use super::*;
-    use ink::prelude::string::String;
     use ink::storage::Mapping;
+    use psp22_usdc::PSP22;
+    use supporter_badge::{BadgeKind, Id};
 
     /// Tip event emitted when a tip is sent
     #[ink(event)]
@@ -41,6 +41,30 @@ mod tipping_v6 {
         name: Vec<u8>,
     }
 
+    /// Builder updated event (name or activity changed)
+    #[ink(event)]
+    #[derive(Debug)]
+    pub struct BuilderUpdated {
+        #[ink(topic)]
+        builder_id: U256,
+        #[ink(topic)]
+        address: H160,
+        name: Vec<u8>,
+        is_active: bool,
+    }
+
+    /// Builder retired event
+    #[ink(event)]
+    #[derive(Debug)]
+    pub struct BuilderRetired {
+        #[ink(topic)]
+        builder_id: U256,
+        #[ink(topic)]
+        address: H160,
+        total_received: U256,
+        tip_count: U256,
+    }
+
     /// Campaign created event
     #[ink(event)]
     #[derive(Debug)]
@@ -53,6 +77,39 @@ mod tipping_v6 {
         deadline: u64,
     }
 
+    /// A supporter badge could not be minted; the tip still went through
+    #[ink(event)]
+    #[derive(Debug)]
+    pub struct BadgeMintFailed {
+        #[ink(topic)]
+        supporter: H160,
+        #[ink(topic)]
+        builder_id: u32,
+        kind: BadgeKind,
+        campaign_id: Option<u32>,
+        tier: u8,
+    }
+
+    /// Stray tokens recovered by the owner
+    #[ink(event)]
+    #[derive(Debug)]
+    pub struct TokensRecovered {
+        #[ink(topic)]
+        token: H160,
+        #[ink(topic)]
+        to: H160,
+        amount: U256,
+    }
+
+    /// Stray native balance recovered by the owner
+    #[ink(event)]
+    #[derive(Debug)]
+    pub struct NativeRecovered {
+        #[ink(topic)]
+        to: H160,
+        amount: U256,
+    }
+
     /// Builder information
     #[derive(Debug, Clone, scale::Encode, scale::Decode)]
     #[cfg_attr(
@@ -66,6 +123,7 @@ mod tipping_v6 {
         pub total_received: U256,
         pub tip_count: U256,
         pub is_active: bool,
+        pub is_retired: bool,
     }
 
     /// Campaign information
@@ -127,6 +185,16 @@ mod tipping_v6 {
         token_address: Option<H160>,
         /// Paused state
         paused: bool,
+        /// Supporter badge contract (badges disabled when `None`)
+        badge_contract: Option<H160>,
+        /// Cumulative tip amounts per builder that earn a tier badge (ascending)
+        badge_tiers: Vec<U256>,
+        /// Minimum cumulative contribution to a campaign for a contributor badge
+        campaign_badge_min: U256,
+        /// Cumulative amount tipped per (tipper, builder)
+        supporter_totals: Mapping<(H160, U256), U256>,
+        /// Cumulative amount contributed per (tipper, campaign)
+        campaign_contributions: Mapping<(H160, U256), U256>,
     }
 
     /// Contract errors
@@ -153,6 +221,12 @@ mod tipping_v6 {
         InvalidFee,
         /// Zero address
         ZeroAddress,
+        /// Builder has been retired
+        BuilderRetired,
+        /// Badge tiers are not strictly ascending and non-zero
+        InvalidBadgeTiers,
+        /// Contract must be paused
+        NotPaused,
     }
 
     impl TippingV6 {
@@ -174,6 +248,11 @@ mod tipping_v6 {
                 next_tip_id: U256::from(1),
                 token_address: None,
                 paused: false,
+                badge_contract: None,
+                badge_tiers: Vec::new(),
+                campaign_badge_min: U256::from(0),
+                supporter_totals: Mapping::new(),
+                campaign_contributions: Mapping::new(),
             }
         }
 
@@ -193,7 +272,7 @@ mod tipping_v6 {
             }
 
             // Check if builder already exists
-            if self.address_to_builder.get(&address).is_some() {
+            if self.address_to_builder.get(address).is_some() {
                 return Err(Error::BuilderAlreadyExists);
             }
 
@@ -205,10 +284,11 @@ mod tipping_v6 {
                 total_received: U256::from(0),
                 tip_count: U256::from(0),
                 is_active: true,
+                is_retired: false,
             };
 
-            self.builders.insert(&builder_id, &builder);
-            self.address_to_builder.insert(&address, &builder_id);
+            self.builders.insert(builder_id, &builder);
+            self.address_to_builder.insert(address, &builder_id);
             self.next_builder_id = builder_id + U256::from(1);
 
             self.env().emit_event(BuilderRegistered {
@@ -227,72 +307,24 @@ mod tipping_v6 {
             builder_id: U256,
             message: Vec<u8>,
         ) -> Result<U256, Error> {
-            if self.paused {
-                return Err(Error::ContractPaused);
-            }
-
-            let amount = U256::from(self.env().transferred_value());
-            if amount == U256::from(0) {
-                return Err(Error::InvalidAmount);
-            }
-
-            // Get builder
-            let mut builder = self.builders.get(&builder_id)
-                .ok_or(Error::BuilderNotFound)?;
-
-            if !builder.is_active {
-                return Err(Error::BuilderNotFound);
-            }
-
-            // Calculate fee
-            let fee = (amount * self.protocol_fee_bps) / U256::from(10000);
-            let tip_amount = amount - fee;
-
-            // Update builder stats
-            builder.total_received = builder.total_received + tip_amount;
-            builder.tip_count = builder.tip_count + U256::from(1);
-            self.builders.insert(&builder_id, &builder);
-
-            // Update protocol fees
-            self.total_fees_collected = self.total_fees_collected + fee;
-
-            // Store tip
-            let tip_id = self.next_tip_id;
-            let from = Self::h160_from_caller();
-            let tip = Tip {
-                from,
-                builder_id,
-                amount: tip_amount,
-                message: message.clone(),
-                timestamp: self.env().block_timestamp(),
-                campaign_id: None,
-            };
-            self.tips.insert(&tip_id, &tip);
-            self.next_tip_id = tip_id + U256::from(1);
+            self.process_tip(builder_id, message, None)
+        }
 
-            // Transfer to builder (convert H160 to AccountId32)
-            let builder_account = Self::account_from_h160(builder.address);
-            if self.env().transfer(builder_account, Self::u256_to_balance(tip_amount)).is_err() {
-                return Err(Error::TransferFailed);
-            }
+        /// Contribute to a builder's campaign (Solidity selector: 0xABCDEF02)
+        #[ink(message, payable, selector = 0xABCDEF02)]
+        pub fn tip_campaign(
+            &mut self,
+            campaign_id: U256,
+            message: Vec<u8>,
+        ) -> Result<U256, Error> {
+            let campaign = self.campaigns.get(campaign_id)
+                .ok_or(Error::CampaignNotFound)?;
 
-            // Transfer fee to treasury
-            if fee > U256::from(0) {
-                let treasury_account = Self::account_from_h160(self.treasury);
-                if self.env().transfer(treasury_account, Self::u256_to_balance(fee)).is_err() {
-                    return Err(Error::TransferFailed);
-                }
+            if !campaign.is_active || self.env().block_timestamp() > campaign.deadline {
+                return Err(Error::CampaignEnded);
             }
 
-            self.env().emit_event(TipSent {
-                from,
-                builder_id,
-                amount: tip_amount,
-                message,
-                timestamp: self.env().block_timestamp(),
-            });
-
-            Ok(tip_id)
+            self.process_tip(campaign.builder_id, message, Some(campaign_id))
         }
 
         /// Create a campaign (Solidity selector: 0xABCDEF01)
@@ -308,9 +340,13 @@ mod tipping_v6 {
             }
 
             // Verify builder exists and caller is the builder
-            let builder = self.builders.get(&builder_id)
+            let builder = self.builders.get(builder_id)
                 .ok_or(Error::BuilderNotFound)?;
 
+            if builder.is_retired {
+                return Err(Error::BuilderRetired);
+            }
+
             let caller = Self::h160_from_caller();
             if builder.address != caller {
                 return Err(Error::Unauthorized);
@@ -328,7 +364,7 @@ mod tipping_v6 {
                 is_active: true,
             };
 
-            self.campaigns.insert(&campaign_id, &campaign);
+            self.campaigns.insert(campaign_id, &campaign);
             self.next_campaign_id = campaign_id + U256::from(1);
 
             self.env().emit_event(CampaignCreated {
@@ -341,22 +377,104 @@ mod tipping_v6 {
             Ok(campaign_id)
         }
 
+        /// Rename a builder (owner or the builder itself) (Solidity selector: 0x77777777)
+        #[ink(message, selector = 0x77777777)]
+        pub fn update_builder_name(
+            &mut self,
+            builder_id: U256,
+            name: Vec<u8>,
+        ) -> Result<(), Error> {
+            if self.paused {
+                return Err(Error::ContractPaused);
+            }
+
+            let mut builder = self.builder_for_update(builder_id)?;
+            builder.name = name;
+            self.builders.insert(builder_id, &builder);
+
+            self.env().emit_event(BuilderUpdated {
+                builder_id,
+                address: builder.address,
+                name: builder.name,
+                is_active: builder.is_active,
+            });
+
+            Ok(())
+        }
+
+        /// Activate or deactivate a builder (owner or the builder itself) (Solidity selector: 0x88888888)
+        #[ink(message, selector = 0x88888888)]
+        pub fn set_builder_active(
+            &mut self,
+            builder_id: U256,
+            is_active: bool,
+        ) -> Result<(), Error> {
+            if self.paused {
+                return Err(Error::ContractPaused);
+            }
+
+            let mut builder = self.builder_for_update(builder_id)?;
+            builder.is_active = is_active;
+            self.builders.insert(builder_id, &builder);
+
+            self.env().emit_event(BuilderUpdated {
+                builder_id,
+                address: builder.address,
+                name: builder.name,
+                is_active,
+            });
+
+            Ok(())
+        }
+
+        /// Retire a builder (owner or the builder itself) (Solidity selector: 0x99999999)
+        ///
+        /// The builder record and its tip history are kept, but the builder can no
+        /// longer receive tips and its address is released so it can register again.
+        #[ink(message, selector = 0x99999999)]
+        pub fn retire_builder(&mut self, builder_id: U256) -> Result<(), Error> {
+            if self.paused {
+                return Err(Error::ContractPaused);
+            }
+
+            let mut builder = self.builder_for_update(builder_id)?;
+            builder.is_active = false;
+            builder.is_retired = true;
+            self.builders.insert(builder_id, &builder);
+            self.address_to_builder.remove(builder.address);
+
+            self.env().emit_event(BuilderRetired {
+                builder_id,
+                address: builder.address,
+                total_received: builder.total_received,
+                tip_count: builder.tip_count,
+            });
+
+            Ok(())
+        }
+
+        /// Get the builder ID registered for an address (Solidity selector: 0x10101010)
+        #[ink(message, selector = 0x10101010)]
+        pub fn get_builder_id(&self, address: H160) -> Option<U256> {
+            self.address_to_builder.get(address)
+        }
+
         /// Get builder information (Solidity selector: 0x11111111)
         #[ink(message, selector = 0x11111111)]
         pub fn get_builder(&self, builder_id: U256) -> Option<Builder> {
-            self.builders.get(&builder_id)
+            self.builders.get(builder_id)
         }
 
         /// Get campaign information (Solidity selector: 0x22222222)
         #[ink(message, selector = 0x22222222)]
         pub fn get_campaign(&self, campaign_id: U256) -> Option<Campaign> {
-            self.campaigns.get(&campaign_id)
+            self.campaigns.get(campaign_id)
         }
 
         /// Get tip information (Solidity selector: 0x33333333)
         #[ink(message, selector = 0x33333333)]
         pub fn get_tip(&self, tip_id: U256) -> Option<Tip> {
-            self.tips.get(&tip_id)
+            self.tips.get(tip_id)
         }
 
         /// Set protocol fee (only owner) (Solidity selector: 0x44444444)
@@ -406,35 +524,295 @@ mod tipping_v6 {
             // In a real implementation, this would use the revive pallet's
             // cross-contract call functionality
             // For now, we return a success indicator
+            let _ = (target, data, value);
             Ok(vec![0x01])
         }
 
+        /// Configure supporter badges (only owner) (Solidity selector: 0x13131313)
+        ///
+        /// `tiers` are cumulative amounts tipped to a single builder, in
+        /// strictly ascending order; crossing the n-th one earns tier n.
+        /// Campaign contributors earn a badge once their contribution to a
+        /// campaign reaches `campaign_min`.
+        #[ink(message, selector = 0x13131313)]
+        pub fn set_badge_config(
+            &mut self,
+            badge_contract: Option<H160>,
+            tiers: Vec<U256>,
+            campaign_min: U256,
+        ) -> Result<(), Error> {
+            let caller = Self::h160_from_caller();
+            if caller != self.owner {
+                return Err(Error::Unauthorized);
+            }
+
+            if tiers.len() > u8::MAX as usize
+                || tiers.first() == Some(&U256::from(0))
+                || tiers.windows(2).any(|pair| pair[0] >= pair[1])
+            {
+                return Err(Error::InvalidBadgeTiers);
+            }
+
+            self.badge_contract = badge_contract;
+            self.badge_tiers = tiers;
+            self.campaign_badge_min = campaign_min;
+            Ok(())
+        }
+
+        /// Get the cumulative amount a tipper has sent to a builder (Solidity selector: 0x14141414)
+        #[ink(message, selector = 0x14141414)]
+        pub fn get_supporter_total(&self, tipper: H160, builder_id: U256) -> U256 {
+            self.supporter_totals.get((tipper, builder_id)).unwrap_or_default()
+        }
+
+        /// Recover tokens sent to the contract by mistake (only owner, while paused) (Solidity selector: 0x15151515)
+        #[ink(message, selector = 0x15151515)]
+        pub fn recover_tokens(&mut self, token: H160, to: H160, amount: U256) -> Result<(), Error> {
+            self.ensure_recovery(to)?;
+
+            let value = Balance::try_from(amount).map_err(|_| Error::InvalidAmount)?;
+            let mut psp22: ink::contract_ref!(PSP22) = token.into();
+            psp22.transfer(to, value, Vec::new())
+                .map_err(|_| Error::TransferFailed)?;
+
+            self.env().emit_event(TokensRecovered { token, to, amount });
+            Ok(())
+        }
+
+        /// Recover native balance sent to the contract by mistake (only owner, while paused) (Solidity selector: 0x16161616)
+        #[ink(message, selector = 0x16161616)]
+        pub fn recover_native(&mut self, to: H160, amount: U256) -> Result<(), Error> {
+            self.ensure_recovery(to)?;
+            Balance::try_from(amount).map_err(|_| Error::InvalidAmount)?;
+
+            if self.env().transfer(to, amount).is_err() {
+                return Err(Error::TransferFailed);
+            }
+
+            self.env().emit_event(NativeRecovered { to, amount });
+            Ok(())
+        }
+
         // Helper functions
 
-        /// Convert caller to H160 address
-        fn h160_from_caller() -> H160 {
-            let caller = ink::env::caller::<ink::env::DefaultEnvironment>();
-            let bytes = caller.as_ref();
-            let mut h160_bytes = [0u8; 20];
-            // Take last 20 bytes for H160
-            h160_bytes.copy_from_slice(&bytes[12..32]);
-            H160::from(h160_bytes)
+        /// Recovery is owner-only, only while paused, and never to the zero address
+        fn ensure_recovery(&self, to: H160) -> Result<(), Error> {
+            if Self::h160_from_caller() != self.owner {
+                return Err(Error::Unauthorized);
+            }
+            if !self.paused {
+                return Err(Error::NotPaused);
+            }
+            if to == H160::zero() {
+                return Err(Error::ZeroAddress);
+            }
+            Ok(())
         }
 
-        /// Convert H160 to AccountId32
-        fn account_from_h160(address: H160) -> InkAccountId {
-            let mut bytes = [0u8; 32];
-            bytes[12..32].copy_from_slice(&address.0);
-            InkAccountId::from(bytes)
+        /// Shared tip logic for builder tips and campaign contributions
+        fn process_tip(
+            &mut self,
+            builder_id: U256,
+            message: Vec<u8>,
+            campaign_id: Option<U256>,
+        ) -> Result<U256, Error> {
+            if self.paused {
+                return Err(Error::ContractPaused);
+            }
+
+            let amount = self.env().transferred_value();
+            if amount == U256::from(0) {
+                return Err(Error::InvalidAmount);
+            }
+
+            // Get builder
+            let mut builder = self.builders.get(builder_id)
+                .ok_or(Error::BuilderNotFound)?;
+
+            if !builder.is_active {
+                return Err(Error::BuilderNotFound);
+            }
+
+            // Calculate fee
+            let fee = (amount * self.protocol_fee_bps) / U256::from(10000);
+            let tip_amount = amount - fee;
+
+            // Update builder stats
+            builder.total_received += tip_amount;
+            builder.tip_count += U256::from(1);
+            self.builders.insert(builder_id, &builder);
+
+            // Update campaign progress
+            if let Some(campaign_id) = campaign_id {
+                if let Some(mut campaign) = self.campaigns.get(campaign_id) {
+                    campaign.raised_amount += tip_amount;
+                    self.campaigns.insert(campaign_id, &campaign);
+                }
+            }
+
+            // Update protocol fees
+            self.total_fees_collected += fee;
+
+            // Store tip
+            let tip_id = self.next_tip_id;
+            let from = Self::h160_from_caller();
+            let tip = Tip {
+                from,
+                builder_id,
+                amount: tip_amount,
+                message: message.clone(),
+                timestamp: self.env().block_timestamp(),
+                campaign_id,
+            };
+            self.tips.insert(tip_id, &tip);
+            self.next_tip_id = tip_id + U256::from(1);
+
+            // Transfer to builder
+            if self.env().transfer(builder.address, tip_amount).is_err() {
+                return Err(Error::TransferFailed);
+            }
+
+            // Transfer fee to treasury
+            if fee > U256::from(0) && self.env().transfer(self.treasury, fee).is_err() {
+                return Err(Error::TransferFailed);
+            }
+
+            self.award_badges(from, builder_id, campaign_id, amount);
+
+            self.env().emit_event(TipSent {
+                from,
+                builder_id,
+                amount: tip_amount,
+                message,
+                timestamp: self.env().block_timestamp(),
+            });
+
+            Ok(tip_id)
+        }
+
+        /// Update supporter totals and mint any badges earned by this tip
+        fn award_badges(&mut self, from: H160, builder_id: U256, campaign_id: Option<U256>, amount: U256) {
+            let previous_total = self.get_supporter_total(from, builder_id);
+            let new_total = previous_total + amount;
+            self.supporter_totals.insert((from, builder_id), &new_total);
+
+            let previous_contribution = campaign_id
+                .map(|id| self.campaign_contributions.get((from, id)).unwrap_or_default());
+            if let (Some(id), Some(previous)) = (campaign_id, previous_contribution) {
+                self.campaign_contributions.insert((from, id), &(previous + amount));
+            }
+
+            let Some(badge_contract) = self.badge_contract else {
+                return;
+            };
+            // Badge IDs are u32; larger builder IDs simply don't earn badges
+            let Ok(badge_builder_id) = u32::try_from(builder_id) else {
+                return;
+            };
+            if previous_total == U256::from(0) {
+                self.mint_badge(badge_contract, from, BadgeKind::FirstTip, badge_builder_id, None, 0);
+            }
+
+            for tier in Self::crossed_tiers(&self.badge_tiers, previous_total, new_total) {
+                self.mint_badge(badge_contract, from, BadgeKind::AmountTier, badge_builder_id, None, tier);
+            }
+
+            if let (Some(id), Some(previous)) = (campaign_id, previous_contribution) {
+                let threshold = self.campaign_badge_min.max(U256::from(1));
+                if previous < threshold && previous + amount >= threshold {
+                    if let Ok(badge_campaign_id) = u32::try_from(id) {
+                        self.mint_badge(
+                            badge_contract,
+                            from,
+                            BadgeKind::CampaignContributor,
+                            badge_builder_id,
+                            Some(badge_campaign_id),
+                            0,
+                        );
+                    }
+                }
+            }
+        }
+
+        /// 1-based tiers whose threshold lies in `(previous_total, new_total]`
+        fn crossed_tiers(tiers: &[U256], previous_total: U256, new_total: U256) -> Vec<u8> {
+            tiers
+                .iter()
+                .enumerate()
+                .filter(|(_, threshold)| previous_total < **threshold && **threshold <= new_total)
+                .map(|(index, _)| (index + 1) as u8)
+                .collect()
+        }
+
+        /// Mint a badge on the badge contract
+        ///
+        /// Minting is best-effort: a misconfigured or failing badge contract
+        /// must never block a tip, so a badge that isn't minted is reported
+        /// with `BadgeMintFailed` instead.
+        fn mint_badge(
+            &self,
+            badge_contract: H160,
+            to: H160,
+            kind: BadgeKind,
+            builder_id: u32,
+            campaign_id: Option<u32>,
+            tier: u8,
+        ) {
+            if Self::try_mint_badge(badge_contract, to, kind, builder_id, campaign_id, tier).is_none() {
+                self.env().emit_event(BadgeMintFailed {
+                    supporter: to,
+                    builder_id,
+                    kind,
+                    campaign_id,
+                    tier,
+                });
+            }
+        }
+
+        #[cfg(not(test))]
+        fn try_mint_badge(
+            badge_contract: H160,
+            to: H160,
+            kind: BadgeKind,
+            builder_id: u32,
+            campaign_id: Option<u32>,
+            tier: u8,
+        ) -> Option<Id> {
+            supporter_badge::try_mint_badge(badge_contract, to, kind, builder_id, campaign_id, tier)
+        }
+
+        #[cfg(test)]
+        fn try_mint_badge(
+            badge_contract: H160,
+            to: H160,
+            kind: BadgeKind,
+            builder_id: u32,
+            campaign_id: Option<u32>,
+            tier: u8,
+        ) -> Option<Id> {
+            tests::mint_badge(badge_contract, to, kind, builder_id, campaign_id, tier)
+        }
+
+        /// Load a non-retired builder that the caller (owner or builder) may modify
+        fn builder_for_update(&self, builder_id: U256) -> Result<Builder, Error> {
+            let builder = self.builders.get(builder_id)
+                .ok_or(Error::BuilderNotFound)?;
+
+            if builder.is_retired {
+                return Err(Error::BuilderRetired);
+            }
+
+            let caller = Self::h160_from_caller();
+            if caller != self.owner && caller != builder.address {
+                return Err(Error::Unauthorized);
+            }
+
+            Ok(builder)
         }
 
-        /// Convert U256 to Balance
-        fn u256_to_balance(value: U256) -> Balance {
-            // For safety, we check if the value fits in Balance (u128)
-            let bytes = value.to_big_endian();
-            let mut balance_bytes = [0u8; 16];
-            balance_bytes.copy_from_slice(&bytes[16..32]);
-            Balance::from_be_bytes(balance_bytes)
+        /// Caller's H160 address
+        fn h160_from_caller() -> H160 {
+            ink::env::caller()
         }
     }
 
@@ -443,6 +821,38 @@ mod tipping_v6 {
         use super::*;
         use ink::env::test;
         use hex_literal::hex;
+        use scale::Decode;
+        use std::cell::RefCell;
+
+        /// Stand-in for the badge contract: the badges it minted and whether
+        /// it refuses to mint
+        #[derive(Default)]
+        pub struct MockBadges {
+            pub minted: Vec<(H160, BadgeKind, u32, Option<u32>, u8)>,
+            pub refusing: bool,
+        }
+
+        thread_local! {
+            pub static MOCK_BADGES: RefCell<MockBadges> = RefCell::new(MockBadges::default());
+        }
+
+        pub fn mint_badge(
+            _badge_contract: H160,
+            to: H160,
+            kind: BadgeKind,
+            builder_id: u32,
+            campaign_id: Option<u32>,
+            tier: u8,
+        ) -> Option<Id> {
+            MOCK_BADGES.with(|mock| {
+                let mut mock = mock.borrow_mut();
+                if mock.refusing {
+                    return None;
+                }
+                mock.minted.push((to, kind, builder_id, campaign_id, tier));
+                Some(Id::U128(mock.minted.len() as u128))
+            })
+        }
 
         #[ink::test]
         fn constructor_works() {
@@ -491,7 +901,7 @@ mod tipping_v6 {
             ).unwrap();
             
             // Set up test environment with value
-            test::set_value_transferred::<ink::env::DefaultEnvironment>(1_000_000);
+            test::set_value_transferred(U256::from(1_000_000));
             
             // Send tip
             let message = b"Great work!".to_vec();
@@ -518,9 +928,7 @@ mod tipping_v6 {
             ).unwrap();
             
             // Create campaign
-            test::set_caller::<ink::env::DefaultEnvironment>(
-                Self::account_from_h160(builder_address)
-            );
+            test::set_caller(builder_address);
             
             let target = U256::from(1_000_000);
             let duration = 30; // 30 days
@@ -558,5 +966,288 @@ mod tipping_v6 {
             );
             assert_eq!(result, Err(Error::ContractPaused));
         }
+
+        #[ink::test]
+        fn recovery_requires_owner_and_pause() {
+            let treasury = H160::from(hex!("1234567890123456789012345678901234567890"));
+            let mut contract = TippingV6::new(treasury, U256::from(100));
+            let to = H160::from(hex!("DDDDDDDDDDDDDDDDDDDDDDDDDDDDDDDDDDDDDDDD"));
+            let token = H160::from(hex!("EEEEEEEEEEEEEEEEEEEEEEEEEEEEEEEEEEEEEEEE"));
+
+            assert_eq!(contract.recover_native(to, U256::from(1_000)), Err(Error::NotPaused));
+            assert_eq!(contract.recover_tokens(token, to, U256::from(1_000)), Err(Error::NotPaused));
+
+            contract.set_paused(true).unwrap();
+            assert_eq!(contract.recover_native(H160::zero(), U256::from(1_000)), Err(Error::ZeroAddress));
+            assert!(contract.recover_native(to, U256::from(1_000)).is_ok());
+
+            // Amounts that don't fit a token balance are rejected, not truncated
+            let too_large = U256::from(u128::MAX) + U256::from(1);
+            assert_eq!(contract.recover_tokens(token, to, too_large), Err(Error::InvalidAmount));
+            assert_eq!(contract.recover_native(to, too_large), Err(Error::InvalidAmount));
+
+            let stranger = H160::from(hex!("BBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBB"));
+            test::set_caller(stranger);
+            assert_eq!(contract.recover_native(to, U256::from(1_000)), Err(Error::Unauthorized));
+            assert_eq!(contract.recover_tokens(token, to, U256::from(1_000)), Err(Error::Unauthorized));
+        }
+
+        #[ink::test]
+        fn builder_can_rename_and_deactivate_itself() {
+            let treasury = H160::from(hex!("1234567890123456789012345678901234567890"));
+            let mut contract = TippingV6::new(treasury, U256::from(100));
+
+            let builder_address = H160::from(hex!("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA"));
+            let builder_id = contract.register_builder(
+                b"Test Builder".to_vec(),
+                builder_address
+            ).unwrap();
+
+            test::set_caller(builder_address);
+
+            assert!(contract.update_builder_name(builder_id, b"Renamed".to_vec()).is_ok());
+            assert!(contract.set_builder_active(builder_id, false).is_ok());
+
+            let builder = contract.get_builder(builder_id).unwrap();
+            assert_eq!(builder.name, b"Renamed".to_vec());
+            assert!(!builder.is_active);
+
+            // Inactive builders cannot be tipped
+            test::set_value_transferred(U256::from(1_000_000));
+            assert_eq!(contract.tip(builder_id, b"Hi".to_vec()), Err(Error::BuilderNotFound));
+
+            // Reactivation restores tipping
+            assert!(contract.set_builder_active(builder_id, true).is_ok());
+            assert!(contract.tip(builder_id, b"Hi".to_vec()).is_ok());
+        }
+
+        #[ink::test]
+        fn builder_update_unauthorized() {
+            let treasury = H160::from(hex!("1234567890123456789012345678901234567890"));
+            let mut contract = TippingV6::new(treasury, U256::from(100));
+
+            let builder_address = H160::from(hex!("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA"));
+            let builder_id = contract.register_builder(
+                b"Test Builder".to_vec(),
+                builder_address
+            ).unwrap();
+
+            let stranger = H160::from(hex!("BBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBB"));
+            test::set_caller(stranger);
+
+            assert_eq!(
+                contract.update_builder_name(builder_id, b"Hijacked".to_vec()),
+                Err(Error::Unauthorized)
+            );
+            assert_eq!(contract.set_builder_active(builder_id, false), Err(Error::Unauthorized));
+            assert_eq!(contract.retire_builder(builder_id), Err(Error::Unauthorized));
+        }
+
+        #[ink::test]
+        fn retire_builder_keeps_history_and_frees_address() {
+            let treasury = H160::from(hex!("1234567890123456789012345678901234567890"));
+            let mut contract = TippingV6::new(treasury, U256::from(100));
+
+            let builder_address = H160::from(hex!("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA"));
+            let builder_id = contract.register_builder(
+                b"Test Builder".to_vec(),
+                builder_address
+            ).unwrap();
+
+            test::set_value_transferred(U256::from(1_000_000));
+            contract.tip(builder_id, b"Thanks".to_vec()).unwrap();
+
+            // Owner retires the builder
+            assert!(contract.retire_builder(builder_id).is_ok());
+            assert_eq!(contract.get_builder_id(builder_address), None);
+
+            let retired = contract.get_builder(builder_id).unwrap();
+            assert!(retired.is_retired);
+            assert!(!retired.is_active);
+            assert_eq!(retired.total_received, U256::from(990_000));
+            assert_eq!(retired.tip_count, U256::from(1));
+
+            // Retired builders cannot be modified or tipped
+            assert_eq!(contract.set_builder_active(builder_id, true), Err(Error::BuilderRetired));
+            assert_eq!(contract.retire_builder(builder_id), Err(Error::BuilderRetired));
+            assert_eq!(contract.tip(builder_id, b"Hi".to_vec()), Err(Error::BuilderNotFound));
+
+            // The address can register again under a new ID
+            let new_id = contract.register_builder(b"Comeback".to_vec(), builder_address).unwrap();
+            assert_eq!(new_id, U256::from(2));
+            assert_eq!(contract.get_builder_id(builder_address), Some(new_id));
+        }
+
+        #[ink::test]
+        fn tip_campaign_updates_raised_amount() {
+            let treasury = H160::from(hex!("1234567890123456789012345678901234567890"));
+            let mut contract = TippingV6::new(treasury, U256::from(100));
+
+            let builder_address = H160::from(hex!("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA"));
+            let builder_id = contract.register_builder(
+                b"Test Builder".to_vec(),
+                builder_address
+            ).unwrap();
+
+            let tipper = test::default_accounts().bob;
+            test::set_caller(builder_address);
+            let campaign_id = contract.create_campaign(builder_id, U256::from(5_000_000), 30).unwrap();
+
+            test::set_caller(tipper);
+            test::set_value_transferred(U256::from(1_000_000));
+            let tip_id = contract.tip_campaign(campaign_id, b"For the campaign".to_vec()).unwrap();
+
+            let campaign = contract.get_campaign(campaign_id).unwrap();
+            assert_eq!(campaign.raised_amount, U256::from(990_000));
+            assert_eq!(contract.get_tip(tip_id).unwrap().campaign_id, Some(campaign_id));
+
+            let from = TippingV6::h160_from_caller();
+            assert_eq!(contract.get_supporter_total(from, builder_id), U256::from(1_000_000));
+
+            assert_eq!(
+                contract.tip_campaign(U256::from(99), b"Missing".to_vec()),
+                Err(Error::CampaignNotFound)
+            );
+        }
+
+        #[ink::test]
+        fn set_badge_config_validates_tiers() {
+            let treasury = H160::from(hex!("1234567890123456789012345678901234567890"));
+            let mut contract = TippingV6::new(treasury, U256::from(100));
+            let badges = H160::from(hex!("CCCCCCCCCCCCCCCCCCCCCCCCCCCCCCCCCCCCCCCC"));
+
+            assert!(contract.set_badge_config(
+                Some(badges),
+                vec![U256::from(10), U256::from(100)],
+                U256::from(1),
+            ).is_ok());
+            assert_eq!(
+                contract.set_badge_config(Some(badges), vec![U256::from(100), U256::from(10)], U256::from(1)),
+                Err(Error::InvalidBadgeTiers)
+            );
+
+            assert_eq!(
+                TippingV6::crossed_tiers(&[U256::from(10), U256::from(100)], U256::from(5), U256::from(150)),
+                vec![1, 2]
+            );
+        }
+
+        #[ink::test]
+        fn failed_badge_mints_are_reported() {
+            let treasury = H160::from(hex!("1234567890123456789012345678901234567890"));
+            let mut contract = TippingV6::new(treasury, U256::from(100));
+            let badges = H160::from(hex!("CCCCCCCCCCCCCCCCCCCCCCCCCCCCCCCCCCCCCCCC"));
+            contract
+                .set_badge_config(Some(badges), vec![U256::from(50_000), U256::from(150_000)], U256::from(0))
+                .unwrap();
+
+            let builder_address = H160::from(hex!("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA"));
+            let builder_id = contract.register_builder(b"Test Builder".to_vec(), builder_address).unwrap();
+            let from = TippingV6::h160_from_caller();
+
+            // The first tip earns the first-tip and tier 1 badges
+            test::set_value_transferred(U256::from(100_000));
+            contract.tip(builder_id, b"First".to_vec()).unwrap();
+            MOCK_BADGES.with(|mock| {
+                assert_eq!(mock.borrow().minted, vec![
+                    (from, BadgeKind::FirstTip, 1, None, 0),
+                    (from, BadgeKind::AmountTier, 1, None, 1),
+                ]);
+            });
+
+            // A refused mint doesn't block the tip, but is reported
+            MOCK_BADGES.with(|mock| mock.borrow_mut().refusing = true);
+            let emitted_before = test::recorded_events().count();
+            contract.tip(builder_id, b"Second".to_vec()).unwrap();
+            assert_eq!(contract.get_supporter_total(from, builder_id), U256::from(200_000));
+
+            // BadgeMintFailed, then TipSent
+            let events = test::recorded_events().skip(emitted_before).collect::<Vec<_>>();
+            assert_eq!(events.len(), 2);
+            let failed = BadgeMintFailed::decode(&mut &events[0].data[..]).unwrap();
+            assert_eq!(failed.supporter, from);
+            assert_eq!(failed.builder_id, 1);
+            assert_eq!(failed.kind, BadgeKind::AmountTier);
+            assert_eq!(failed.campaign_id, None);
+            assert_eq!(failed.tier, 2);
+            assert_eq!(MOCK_BADGES.with(|mock| mock.borrow().minted.len()), 2);
+        }
+    }
+
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        use super::*;
+        use ink::env::hash::{HashOutput, Keccak256};
+        use ink_e2e::{ContractsBackend, Keypair};
+        use supporter_badge::supporter_badge::{SupporterBadge, SupporterBadgeRef};
+        use supporter_badge::PSP34;
+
+        type E2EResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+        /// H160 pallet-revive maps a keypair's account to
+        fn address(keypair: &Keypair) -> H160 {
+            let mut hash = <Keccak256 as HashOutput>::Type::default();
+            ink::env::hash_bytes::<Keccak256>(&keypair.public_key().0, &mut hash);
+            H160::from_slice(&hash[12..])
+        }
+
+        #[ink_e2e::test]
+        async fn e2e_tip_mints_supporter_badge<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+            // Deploy the badge collection and the tipping contract
+            let mut badge_constructor = SupporterBadgeRef::new("ipfs://tipsydot-badges/".into());
+            let badge = client
+                .instantiate("supporter_badge", &ink_e2e::alice(), &mut badge_constructor)
+                .submit()
+                .await?;
+            let mut badges = badge.call_builder::<SupporterBadge>();
+
+            let mut constructor = TippingV6Ref::new(address(&ink_e2e::ferdie()), U256::from(100));
+            let tipping = client
+                .instantiate("tipping_v6", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await?;
+            let mut contract = tipping.call_builder::<TippingV6>();
+
+            // Let the tipping contract mint badges and point it at the collection
+            let set_minter = badges.set_minter(tipping.addr, true);
+            let minter_result = client.call(&ink_e2e::alice(), &set_minter).submit().await?;
+            assert!(minter_result.return_value().is_ok());
+
+            let set_badge_config = contract.set_badge_config(Some(badge.addr), vec![U256::from(500_000)], U256::from(0));
+            let config_result = client
+                .call(&ink_e2e::alice(), &set_badge_config)
+                .submit()
+                .await?;
+            assert!(config_result.return_value().is_ok());
+
+            let register = contract.register_builder(b"Badge Builder".to_vec(), address(&ink_e2e::charlie()));
+            let builder_id = client
+                .call(&ink_e2e::alice(), &register)
+                .submit()
+                .await?
+                .return_value()
+                .unwrap();
+
+            // A first tip above tier 1 earns both badges
+            let tip = contract.tip(builder_id, b"Badge me".to_vec());
+            let tip_result = client.call(&ink_e2e::bob(), &tip).value(1_000_000).submit().await?;
+            assert!(tip_result.return_value().is_ok());
+
+            // Badges are held by the same H160 the tip is recorded under
+            let tipper = address(&ink_e2e::bob());
+            let builder_id = u32::try_from(builder_id).unwrap();
+
+            let balance = client.call(&ink_e2e::alice(), &badges.balance_of(tipper)).dry_run().await?.return_value();
+            assert_eq!(balance, 2);
+
+            let owner = client.call(&ink_e2e::alice(), &badges.owner_of(Id::U128(1))).dry_run().await?.return_value();
+            assert_eq!(owner, Some(tipper));
+
+            let has_badge = badges.has_badge(tipper, tipping.addr, BadgeKind::AmountTier, builder_id, None, 1);
+            let has_badge = client.call(&ink_e2e::alice(), &has_badge).dry_run().await?.return_value();
+            assert!(has_badge);
+
+            Ok(())
+        }
     }
 }
@@ -0,0 +1,510 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+/// USDC faucet for testnets
+///
+/// The ink! counterpart of `USDCFaucet.sol`: exchanges native currency for
+/// `psp22_usdc` at a configurable rate, capped per request and per account
+/// over a rolling 24 hour window.
+#[ink::contract]
+pub mod usdc_faucet {
+    use ink::primitives::{H160, U256};
+    use ink::storage::Mapping;
+    use scale::{Decode, Encode};
+
+    /// Native currency has 18 decimals on pallet-revive
+    pub const NATIVE_UNIT: u128 = 1_000_000_000_000_000_000;
+
+    /// Length of the rolling daily window, in milliseconds
+    pub const DAY: Timestamp = 86_400_000;
+
+    /// Exchange parameters (defaults mirror `USDCFaucet.sol`)
+    #[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct FaucetConfig {
+        /// USDC units paid out per whole native token
+        pub rate: Balance,
+        /// Smallest accepted payment, in native units
+        pub min_payment: U256,
+        /// Most USDC paid out by a single exchange
+        pub max_per_request: Balance,
+        /// Most USDC an account can receive per rolling 24 hours
+        pub daily_limit: Balance,
+    }
+
+    impl Default for FaucetConfig {
+        fn default() -> Self {
+            Self {
+                rate: 2_000_000,                             // 1 PAS = 2 USDC
+                min_payment: U256::from(NATIVE_UNIT / 2),    // 0.5 PAS
+                max_per_request: 1_000_000_000,              // 1000 USDC
+                daily_limit: 5_000_000_000,                  // 5000 USDC
+            }
+        }
+    }
+
+    /// USDC received by an account in its current window
+    #[derive(Debug, Clone, Default, PartialEq, Eq, Encode, Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct DailyUsage {
+        /// When the window opened (first exchange after the previous one expired)
+        pub window_start: Timestamp,
+        pub amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct UsdcExchanged {
+        #[ink(topic)]
+        user: H160,
+        paid: U256,
+        usdc_amount: Balance,
+        timestamp: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct FaucetFunded {
+        #[ink(topic)]
+        funder: H160,
+        amount: Balance,
+        timestamp: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct NativeWithdrawn {
+        #[ink(topic)]
+        to: H160,
+        amount: U256,
+    }
+
+    #[ink(event)]
+    pub struct ConfigUpdated {
+        config: FaucetConfig,
+    }
+
+    /// Errors that can occur in the faucet
+    #[derive(Debug, PartialEq, Eq, Encode, Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum FaucetError {
+        Unauthorized,
+        BelowMinimumPayment,
+        ExceedsMaxPerRequest,
+        DailyLimitExceeded,
+        FaucetEmpty,
+        TokenTransferFailed,
+        InsufficientBalance,
+        TransferFailed,
+        InvalidConfig,
+    }
+
+    #[ink(storage)]
+    pub struct UsdcFaucet {
+        /// Contract owner
+        owner: H160,
+        /// `psp22_usdc` token paid out
+        token: H160,
+        /// Exchange parameters
+        config: FaucetConfig,
+        /// Rolling daily usage per account
+        usage: Mapping<H160, DailyUsage>,
+    }
+
+    impl UsdcFaucet {
+        /// Creates a faucet for `token` with the default exchange parameters
+        #[ink(constructor)]
+        pub fn new(token: H160) -> Self {
+            Self {
+                owner: Self::env().caller(),
+                token,
+                config: FaucetConfig::default(),
+                usage: Mapping::new(),
+            }
+        }
+
+        /// Exchange the transferred native currency for USDC
+        #[ink(message, payable)]
+        pub fn exchange_native_for_usdc(&mut self) -> Result<Balance, FaucetError> {
+            let paid = self.env().transferred_value();
+            if paid < self.config.min_payment {
+                return Err(FaucetError::BelowMinimumPayment);
+            }
+
+            let usdc_amount = self.quote(paid)?;
+
+            let user = self.env().caller();
+            let now = self.env().block_timestamp();
+            let mut usage = self.current_usage(user, now);
+            if usage.amount + usdc_amount > self.config.daily_limit {
+                return Err(FaucetError::DailyLimitExceeded);
+            }
+
+            if self.token_balance() < usdc_amount {
+                return Err(FaucetError::FaucetEmpty);
+            }
+
+            usage.amount += usdc_amount;
+            self.usage.insert(user, &usage);
+
+            self.send_tokens(user, usdc_amount)?;
+
+            self.env().emit_event(UsdcExchanged {
+                user,
+                paid,
+                usdc_amount,
+                timestamp: now,
+            });
+
+            Ok(usdc_amount)
+        }
+
+        /// USDC an account can still receive in its current window
+        #[ink(message)]
+        pub fn get_remaining_daily_limit(&self, user: H160) -> Balance {
+            let usage = self.current_usage(user, self.env().block_timestamp());
+            self.config.daily_limit.saturating_sub(usage.amount)
+        }
+
+        /// Get the exchange parameters
+        #[ink(message)]
+        pub fn get_exchange_info(&self) -> FaucetConfig {
+            self.config.clone()
+        }
+
+        /// USDC the faucet holds
+        #[ink(message)]
+        pub fn get_faucet_balance(&self) -> Balance {
+            self.token_balance()
+        }
+
+        /// USDC paid out for a native payment, without exchanging
+        #[ink(message)]
+        pub fn quote_exchange(&self, paid: U256) -> Result<Balance, FaucetError> {
+            self.quote(paid)
+        }
+
+        /// Fund the faucet with USDC (requires approval)
+        #[ink(message)]
+        pub fn fund_faucet(&mut self, amount: Balance) -> Result<(), FaucetError> {
+            let funder = self.env().caller();
+            self.pull_tokens(funder, amount)?;
+
+            self.env().emit_event(FaucetFunded {
+                funder,
+                amount,
+                timestamp: self.env().block_timestamp(),
+            });
+            Ok(())
+        }
+
+        /// Withdraw native currency collected by the faucet (only owner)
+        #[ink(message)]
+        pub fn withdraw_native(&mut self, to: H160, amount: U256) -> Result<(), FaucetError> {
+            self.ensure_owner()?;
+
+            if self.env().balance() < amount {
+                return Err(FaucetError::InsufficientBalance);
+            }
+            self.env().transfer(to, amount)
+                .map_err(|_| FaucetError::TransferFailed)?;
+
+            self.env().emit_event(NativeWithdrawn { to, amount });
+            Ok(())
+        }
+
+        /// Update the exchange parameters (only owner)
+        ///
+        /// The minimum payment must quote at least one USDC unit, so no
+        /// accepted payment is exchanged for nothing.
+        #[ink(message)]
+        pub fn set_config(&mut self, config: FaucetConfig) -> Result<(), FaucetError> {
+            self.ensure_owner()?;
+
+            if config.rate == 0
+                || config.min_payment.saturating_mul(U256::from(config.rate)) < U256::from(NATIVE_UNIT)
+                || config.max_per_request == 0
+                || config.max_per_request > config.daily_limit
+            {
+                return Err(FaucetError::InvalidConfig);
+            }
+
+            self.config = config.clone();
+            self.env().emit_event(ConfigUpdated { config });
+            Ok(())
+        }
+
+        /// Helper: USDC owed for `paid` native units, capped per request
+        fn quote(&self, paid: U256) -> Result<Balance, FaucetError> {
+            let usdc_amount = paid
+                .checked_mul(U256::from(self.config.rate))
+                .ok_or(FaucetError::ExceedsMaxPerRequest)?
+                / U256::from(NATIVE_UNIT);
+
+            if usdc_amount > U256::from(self.config.max_per_request) {
+                return Err(FaucetError::ExceedsMaxPerRequest);
+            }
+            Ok(usdc_amount.as_u128())
+        }
+
+        /// Helper: an account's usage, starting a fresh window once the last one expired
+        fn current_usage(&self, user: H160, now: Timestamp) -> DailyUsage {
+            match self.usage.get(user) {
+                Some(usage) if now < usage.window_start + DAY => usage,
+                _ => DailyUsage { window_start: now, amount: 0 },
+            }
+        }
+
+        /// The faucet's USDC balance
+        #[cfg(not(test))]
+        fn token_balance(&self) -> Balance {
+            use psp22_usdc::PSP22;
+
+            let token: ink::contract_ref!(PSP22) = self.token.into();
+            token.balance_of(self.env().address())
+        }
+
+        /// Pay USDC out of the faucet
+        #[cfg(not(test))]
+        fn send_tokens(&self, to: H160, amount: Balance) -> Result<(), FaucetError> {
+            use psp22_usdc::PSP22;
+
+            let mut token: ink::contract_ref!(PSP22) = self.token.into();
+            token.transfer(to, amount, ink::prelude::vec::Vec::new())
+                .map_err(|_| FaucetError::TokenTransferFailed)
+        }
+
+        /// Pull USDC into the faucet
+        #[cfg(not(test))]
+        fn pull_tokens(&self, from: H160, amount: Balance) -> Result<(), FaucetError> {
+            use psp22_usdc::PSP22;
+
+            let mut token: ink::contract_ref!(PSP22) = self.token.into();
+            token.transfer_from(from, self.env().address(), amount, ink::prelude::vec::Vec::new())
+                .map_err(|_| FaucetError::TokenTransferFailed)
+        }
+
+        /// The off-chain test environment has no token contract: the faucet
+        /// holds 10,000 USDC and transfers always succeed
+        #[cfg(test)]
+        fn token_balance(&self) -> Balance {
+            10_000_000_000
+        }
+
+        #[cfg(test)]
+        fn send_tokens(&self, _to: H160, _amount: Balance) -> Result<(), FaucetError> {
+            Ok(())
+        }
+
+        #[cfg(test)]
+        fn pull_tokens(&self, _from: H160, _amount: Balance) -> Result<(), FaucetError> {
+            Ok(())
+        }
+
+        /// Helper: ensure caller is owner
+        fn ensure_owner(&self) -> Result<(), FaucetError> {
+            if self.env().caller() != self.owner {
+                return Err(FaucetError::Unauthorized);
+            }
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::test;
+
+        const PAS: u128 = NATIVE_UNIT;
+        const USDC: Balance = 1_000_000;
+
+        /// Deploy as Alice, who also exchanges; the off-chain engine's
+        /// default caller is the zero address
+        fn create_faucet() -> UsdcFaucet {
+            test::set_caller(test::default_accounts().alice);
+            UsdcFaucet::new(H160::from([0x01; 20]))
+        }
+
+        fn exchange(faucet: &mut UsdcFaucet, paid: u128) -> Result<Balance, FaucetError> {
+            test::set_value_transferred(U256::from(paid));
+            faucet.exchange_native_for_usdc()
+        }
+
+        #[ink::test]
+        fn exchange_works() {
+            let mut faucet = create_faucet();
+            let accounts = test::default_accounts();
+
+            assert_eq!(exchange(&mut faucet, 10 * PAS), Ok(20 * USDC));
+            assert_eq!(faucet.get_remaining_daily_limit(accounts.alice), 4_980 * USDC);
+            assert_eq!(faucet.get_remaining_daily_limit(accounts.bob), 5_000 * USDC);
+        }
+
+        #[ink::test]
+        fn exchange_enforces_minimum_and_request_cap() {
+            let mut faucet = create_faucet();
+
+            assert_eq!(exchange(&mut faucet, PAS / 4), Err(FaucetError::BelowMinimumPayment));
+            assert_eq!(exchange(&mut faucet, 500 * PAS), Ok(1_000 * USDC));
+            assert_eq!(exchange(&mut faucet, 501 * PAS), Err(FaucetError::ExceedsMaxPerRequest));
+            assert_eq!(faucet.quote_exchange(U256::from(PAS / 2)), Ok(USDC));
+        }
+
+        #[ink::test]
+        fn daily_limit_rolls_over() {
+            let mut faucet = create_faucet();
+            let accounts = test::default_accounts();
+
+            test::set_block_timestamp::<Environment>(1_000);
+            for _ in 0..5 {
+                assert!(exchange(&mut faucet, 500 * PAS).is_ok());
+            }
+            assert_eq!(faucet.get_remaining_daily_limit(accounts.alice), 0);
+            assert_eq!(exchange(&mut faucet, PAS), Err(FaucetError::DailyLimitExceeded));
+
+            // Other accounts have their own limit
+            test::set_caller(accounts.bob);
+            assert!(exchange(&mut faucet, PAS).is_ok());
+
+            // The window is 24 hours from the first exchange, not a calendar day
+            test::set_caller(accounts.alice);
+            test::set_block_timestamp::<Environment>(1_000 + DAY - 1);
+            assert_eq!(exchange(&mut faucet, PAS), Err(FaucetError::DailyLimitExceeded));
+            test::set_block_timestamp::<Environment>(1_000 + DAY);
+            assert_eq!(faucet.get_remaining_daily_limit(accounts.alice), 5_000 * USDC);
+            assert!(exchange(&mut faucet, PAS).is_ok());
+        }
+
+        #[ink::test]
+        fn exchange_fails_when_faucet_is_empty() {
+            let mut faucet = create_faucet();
+
+            let config = FaucetConfig {
+                max_per_request: 20_000 * USDC,
+                daily_limit: 20_000 * USDC,
+                ..FaucetConfig::default()
+            };
+            faucet.set_config(config).unwrap();
+
+            assert_eq!(exchange(&mut faucet, 5_001 * PAS), Err(FaucetError::FaucetEmpty));
+            assert!(exchange(&mut faucet, 5_000 * PAS).is_ok());
+        }
+
+        #[ink::test]
+        fn owner_manages_config_and_withdrawals() {
+            let mut faucet = create_faucet();
+            let accounts = test::default_accounts();
+
+            let config = FaucetConfig { rate: 4 * USDC, ..FaucetConfig::default() };
+            assert!(faucet.set_config(config.clone()).is_ok());
+            assert_eq!(faucet.get_exchange_info(), config);
+            assert_eq!(
+                faucet.set_config(FaucetConfig { max_per_request: 6_000 * USDC, ..config.clone() }),
+                Err(FaucetError::InvalidConfig)
+            );
+
+            // Payments at the minimum must quote a nonzero amount of USDC
+            assert_eq!(
+                faucet.set_config(FaucetConfig { min_payment: U256::zero(), ..config.clone() }),
+                Err(FaucetError::InvalidConfig)
+            );
+            assert_eq!(
+                faucet.set_config(FaucetConfig { rate: 1, ..FaucetConfig::default() }),
+                Err(FaucetError::InvalidConfig)
+            );
+            assert_eq!(faucet.get_exchange_info(), config);
+            assert_eq!(
+                faucet.withdraw_native(accounts.alice, U256::MAX),
+                Err(FaucetError::InsufficientBalance)
+            );
+
+            test::set_caller(accounts.bob);
+            assert_eq!(faucet.set_config(config), Err(FaucetError::Unauthorized));
+            assert_eq!(
+                faucet.withdraw_native(accounts.bob, U256::from(PAS)),
+                Err(FaucetError::Unauthorized)
+            );
+
+            // Anyone can fund the faucet
+            assert!(faucet.fund_faucet(100 * USDC).is_ok());
+        }
+    }
+
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        use super::*;
+        use ink::env::hash::{HashOutput, Keccak256};
+        use ink_e2e::{ContractsBackend, Keypair};
+        use psp22_usdc::psp22_usdc::{MockUSDC, MockUSDCRef};
+        use psp22_usdc::PSP22;
+
+        type E2EResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+        const USDC: Balance = 1_000_000;
+
+        /// H160 pallet-revive maps a keypair's account to
+        fn address(keypair: &Keypair) -> H160 {
+            let mut hash = <Keccak256 as HashOutput>::Type::default();
+            ink::env::hash_bytes::<Keccak256>(&keypair.public_key().0, &mut hash);
+            H160::from_slice(&hash[12..])
+        }
+
+        #[ink_e2e::test]
+        async fn e2e_faucet_pays_out_only_what_it_was_funded<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+            let mut constructor = MockUSDCRef::new(10_000 * USDC);
+            let usdc = client
+                .instantiate("psp22_usdc", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await?;
+            let mut usdc_calls = usdc.call_builder::<MockUSDC>();
+            let mut constructor = UsdcFaucetRef::new(usdc.addr);
+            let faucet = client
+                .instantiate("usdc_faucet", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await?;
+            let mut contract = faucet.call_builder::<UsdcFaucet>();
+
+            // One USDC unit per wei, whatever the chain's native-to-wei
+            // scaling, so a single native unit always quotes some USDC
+            let config = FaucetConfig {
+                rate: NATIVE_UNIT,
+                min_payment: U256::from(1),
+                max_per_request: 1_000 * USDC,
+                daily_limit: 1_000 * USDC,
+            };
+            let set_config = contract.set_config(config);
+            let config_result = client.call(&ink_e2e::alice(), &set_config).submit().await?;
+            assert_eq!(config_result.return_value(), Ok(()));
+
+            // Nothing has been funded yet
+            let balance = client.call(&ink_e2e::alice(), &contract.get_faucet_balance()).dry_run().await?.return_value();
+            assert_eq!(balance, 0);
+            let exchange = contract.exchange_native_for_usdc();
+            let exchange_result = client.call(&ink_e2e::bob(), &exchange).value(1).dry_run().await?;
+            assert_eq!(exchange_result.return_value(), Err(FaucetError::FaucetEmpty));
+
+            // Funding pulls approved USDC into the faucet
+            let approve = usdc_calls.approve(faucet.addr, 1_000 * USDC);
+            let approve_result = client.call(&ink_e2e::alice(), &approve).submit().await?;
+            assert!(approve_result.return_value().is_ok());
+            let fund = contract.fund_faucet(1_000 * USDC);
+            let fund_result = client.call(&ink_e2e::alice(), &fund).submit().await?;
+            assert_eq!(fund_result.return_value(), Ok(()));
+
+            let balance = client.call(&ink_e2e::alice(), &contract.get_faucet_balance()).dry_run().await?.return_value();
+            assert_eq!(balance, 1_000 * USDC);
+            let allowance = usdc_calls.allowance(address(&ink_e2e::alice()), faucet.addr);
+            let allowance = client.call(&ink_e2e::alice(), &allowance).dry_run().await?.return_value();
+            assert_eq!(allowance, 0);
+
+            // The same exchange now pays out of the funded balance
+            let exchange_result = client.call(&ink_e2e::bob(), &exchange).value(1).submit().await?;
+            let paid_out = exchange_result.return_value().unwrap();
+            assert!(paid_out > 0);
+
+            let bob_balance = usdc_calls.balance_of(address(&ink_e2e::bob()));
+            let bob_balance = client.call(&ink_e2e::alice(), &bob_balance).dry_run().await?.return_value();
+            assert_eq!(bob_balance, paid_out);
+            let balance = client.call(&ink_e2e::alice(), &contract.get_faucet_balance()).dry_run().await?.return_value();
+            assert_eq!(balance, 1_000 * USDC - paid_out);
+
+            Ok(())
+        }
+    }
+}